@@ -4,7 +4,7 @@ import React, { useCallback, useState } from "react"
 import { useWallet, useConnection } from "@solana/wallet-adapter-react"
 import { useSfx } from "./useSfx"
 import { TokenSetupClient } from "../../utils/token-setup-client"
-import { AnchorClient } from "../../utils/anchor-client"
+import { AnchorClient, CONSTANT_PRODUCT_CURVE, sortMints } from "../../utils/anchor-client"
 import { COUNTER_HOOK_PROGRAM_ID } from "../../config/program"
 import { useStage } from "./stage"
 import { TOKEN_2022_PROGRAM, ASSOCIATED_TOKEN_PROGRAM } from "../../config/constants"
@@ -173,9 +173,12 @@ export default function Orchestrator({ createdTokens, onTokensCreated, createdPo
     play('click')
     try {
       const client = new AnchorClient(connection, { publicKey, signTransaction } as any)
+      const [mintA, mintB] = sortMints(new web3.PublicKey(createdTokens.tokenA as string), new web3.PublicKey(createdTokens.tokenB as string))
       const solFeeCollector = publicKey
       const solFee = 50_000_000
-      const ammRes = await client.createAmm(new web3.PublicKey(createdTokens.tokenA as string), new web3.PublicKey(createdTokens.tokenB as string), solFee, solFeeCollector as any, signTransaction)
+      // A single 0.3% LP fee, which the AMM's bounds allow and the pool uses
+      const tradeFeeBps = 30
+      const ammRes = await client.createAmm(mintA, mintB, solFee, solFeeCollector as any, tradeFeeBps, tradeFeeBps, signTransaction)
       if (!ammRes.success) {
         setErrorMsg('createAmm failed')
         return
@@ -183,14 +186,14 @@ export default function Orchestrator({ createdTokens, onTokensCreated, createdPo
       if (ammRes.signature) console.log('Create AMM tx:', `https://explorer.solana.com/tx/${ammRes.signature}?cluster=devnet`)
       setStage('amm')
 
-      const poolRes = await client.createPool(new web3.PublicKey(createdTokens.tokenA as string), new web3.PublicKey(createdTokens.tokenB as string), signTransaction)
+      const poolRes = await client.createPool(mintA, mintB, tradeFeeBps, CONSTANT_PRODUCT_CURVE, signTransaction)
       if (!poolRes.success) {
         setErrorMsg('createPool failed')
         return
       }
       if (poolRes.signature) console.log('Create Pool tx:', `https://explorer.solana.com/tx/${poolRes.signature}?cluster=devnet`)
       // Create pool token accounts so pool vaults are set in state
-      const cpta = await client.createPoolTokenAccounts(new web3.PublicKey(createdTokens.tokenA as string), new web3.PublicKey(createdTokens.tokenB as string), poolRes.lpMint as any, poolRes.pool as any, signTransaction)
+      const cpta = await client.createPoolTokenAccounts(mintA, mintB, poolRes.lpMint as any, poolRes.pool as any, signTransaction)
       if (!cpta.success) {
         setErrorMsg('createPoolTokenAccounts failed')
         return
@@ -309,13 +312,18 @@ export default function Orchestrator({ createdTokens, onTokensCreated, createdPo
         amountA: liquidityAmount,
         amountB: liquidityAmount,
       })
+      const [poolMintA, poolMintB] = sortMints(mintAPk, mintBPk)
       const depRes = await client.depositLiquidity(
-        new web3.PublicKey(createdTokens.tokenA as string),
-        new web3.PublicKey(createdTokens.tokenB as string),
+        poolMintA,
+        poolMintB,
         new web3.PublicKey(createdPool.pool as string),
         lpMintPk,
         liquidityAmount,
         liquidityAmount,
+        // The max amounts already cap what is spent, so take whatever LP they buy
+        0,
+        // Give up if the transaction hasn't landed within a minute
+        Math.floor(Date.now() / 1000) + 60,
         new web3.PublicKey(COUNTER_HOOK_PROGRAM_ID),
         signTransaction
       )
@@ -351,14 +359,17 @@ export default function Orchestrator({ createdTokens, onTokensCreated, createdPo
       // Swap 5 tokens
       const inputAmount = Math.floor(5 * scale)
       const minOutputAmount = Math.floor(inputAmount * 0.95)
+      const [mintA, mintB] = sortMints(new web3.PublicKey(createdTokens.tokenA as string), new web3.PublicKey(createdTokens.tokenB as string))
       const res = await client.swapTokens(
         new web3.PublicKey(createdPool.amm as string),
         new web3.PublicKey(createdPool.pool as string),
-        new web3.PublicKey(createdTokens.tokenA as string),
-        new web3.PublicKey(createdTokens.tokenB as string),
-        dir==='AtoB',
+        mintA,
+        mintB,
+        // Directions are in terms of the created tokens; the pool's A is whichever sorts first
+        mintA.equals(new web3.PublicKey(inMint as string)),
         inputAmount,
         minOutputAmount,
+        Math.floor(Date.now() / 1000) + 60,
         new web3.PublicKey(COUNTER_HOOK_PROGRAM_ID),
         new web3.PublicKey(COUNTER_HOOK_PROGRAM_ID),
         signTransaction
@@ -14,16 +14,16 @@ export type Amm = {
   },
   "instructions": [
     {
-      "name": "createAmm",
+      "name": "acceptAdmin",
       "discriminator": [
-        242,
-        91,
-        21,
-        170,
-        5,
-        68,
-        125,
-        64
+        112,
+        42,
+        45,
+        90,
+        116,
+        181,
+        13,
+        170
       ],
       "accounts": [
         {
@@ -40,34 +40,16 @@ export type Amm = {
                 ]
               },
               {
-                "kind": "arg",
+                "kind": "account",
                 "path": "mintA"
               },
               {
-                "kind": "arg",
+                "kind": "account",
                 "path": "mintB"
               }
             ]
           }
         },
-        {
-          "name": "admin",
-          "docs": [
-            "The admin of the AMM"
-          ],
-          "signer": true
-        },
-        {
-          "name": "solFeeCollector",
-          "docs": [
-            "SOL fee collector account"
-          ]
-        },
-        {
-          "name": "authority",
-          "writable": true,
-          "signer": true
-        },
         {
           "name": "mintA"
         },
@@ -75,92 +57,119 @@ export type Amm = {
           "name": "mintB"
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "pendingAdmin",
+          "docs": [
+            "The proposed admin, who has to sign to take over"
+          ],
+          "signer": true
         }
       ],
-      "args": [
+      "args": []
+    },
+    {
+      "name": "cancelParamChange",
+      "discriminator": [
+        150,
+        147,
+        92,
+        108,
+        72,
+        160,
+        224,
+        55
+      ],
+      "accounts": [
         {
-          "name": "mintA",
-          "type": "pubkey"
+          "name": "amm",
+          "relations": [
+            "pendingChange"
+          ]
         },
         {
-          "name": "mintB",
-          "type": "pubkey"
+          "name": "pendingChange",
+          "writable": true
         },
         {
-          "name": "solFee",
-          "type": "u64"
+          "name": "proposer",
+          "writable": true,
+          "relations": [
+            "pendingChange"
+          ]
         },
         {
-          "name": "solFeeCollector",
-          "type": "pubkey"
+          "name": "authority",
+          "docs": [
+            "Holder of the role that may queue the change, or the admin"
+          ],
+          "signer": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "createPool",
+      "name": "closePosition",
       "discriminator": [
-        233,
-        146,
-        209,
-        142,
-        207,
-        104,
-        64,
-        188
+        123,
+        134,
+        81,
+        0,
+        49,
+        68,
+        98,
+        98
       ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "owner",
           "docs": [
-            "The account paying for all rents"
+            "Owner of the position, who gets its rent back"
           ],
           "writable": true,
-          "signer": true
+          "signer": true,
+          "relations": [
+            "position"
+          ]
         },
         {
-          "name": "amm",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  97,
-                  109,
-                  109
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mintA"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
-              }
-            ]
-          }
+          "name": "position",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "collectFees",
+      "discriminator": [
+        164,
+        152,
+        207,
+        99,
+        30,
+        186,
+        19,
+        182
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "amm"
         },
         {
           "name": "pool",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "amm"
-              },
-              {
-                "kind": "account",
-                "path": "mintA"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
-              }
-            ]
-          }
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true
         },
         {
           "name": "poolAuthority",
@@ -200,11 +209,6 @@ export type Amm = {
             ]
           }
         },
-        {
-          "name": "mintLiquidity",
-          "writable": true,
-          "signer": true
-        },
         {
           "name": "mintA"
         },
@@ -212,363 +216,86 @@ export type Amm = {
           "name": "mintB"
         },
         {
-          "name": "systemProgram",
-          "docs": [
-            "Solana ecosystem accounts"
-          ],
-          "address": "11111111111111111111111111111111"
+          "name": "poolAccountA",
+          "writable": true
         },
         {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+          "name": "poolAccountB",
+          "writable": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "createPoolTokenAccounts",
-      "discriminator": [
-        121,
-        90,
-        65,
-        202,
-        12,
-        119,
-        182,
-        213
-      ],
-      "accounts": [
-        {
-          "name": "payer",
-          "docs": [
-            "The account paying for the transaction"
-          ],
+          "name": "ownerAccountA",
           "writable": true,
-          "signer": true
-        },
-        {
-          "name": "amm",
           "pda": {
             "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  97,
-                  109,
-                  109
-                ]
-              },
               {
                 "kind": "account",
-                "path": "mintA"
+                "path": "owner"
               },
               {
                 "kind": "account",
-                "path": "mintB"
-              }
-            ]
-          }
-        },
-        {
-          "name": "pool",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "amm"
+                "path": "tokenProgramA"
               },
               {
                 "kind": "account",
                 "path": "mintA"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
               }
-            ]
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
           }
         },
         {
-          "name": "poolAuthority",
+          "name": "ownerAccountB",
+          "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "account",
-                "path": "pool"
+                "path": "owner"
               },
               {
                 "kind": "account",
-                "path": "mintA"
+                "path": "tokenProgramB"
               },
               {
                 "kind": "account",
                 "path": "mintB"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  112,
-                  111,
-                  111,
-                  108,
-                  95,
-                  97,
-                  117,
-                  116,
-                  104,
-                  111,
-                  114,
-                  105,
-                  116,
-                  121
-                ]
-              }
-            ]
-          }
-        },
-        {
-          "name": "mintA"
-        },
-        {
-          "name": "mintB"
-        },
-        {
-          "name": "lpMint"
-        },
-        {
-          "name": "poolAccountA",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "poolAuthority"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mintA"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
-        },
-        {
-          "name": "poolAccountB",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "poolAuthority"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
-        },
-        {
-          "name": "poolLpAccount",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "poolAuthority"
-              },
-              {
-                "kind": "const",
-                "value": [
-                  6,
-                  221,
-                  246,
-                  225,
-                  215,
-                  101,
-                  161,
-                  147,
-                  217,
-                  203,
-                  225,
-                  70,
-                  206,
-                  235,
-                  121,
-                  172,
-                  28,
-                  180,
-                  133,
-                  237,
-                  95,
-                  91,
-                  55,
-                  145,
-                  58,
-                  140,
-                  245,
-                  133,
-                  126,
-                  255,
-                  0,
-                  169
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "lpMint"
               }
             ],
             "program": {
@@ -611,34 +338,31 @@ export type Amm = {
           }
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgramA",
           "docs": [
-            "Solana ecosystem accounts"
-          ],
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
         }
       ],
       "args": []
     },
     {
-      "name": "depositLiquidity",
+      "name": "collectProtocolFees",
       "discriminator": [
-        245,
-        99,
-        59,
-        25,
-        151,
-        71,
-        233,
-        249
+        22,
+        67,
+        23,
+        98,
+        150,
+        178,
+        70,
+        220
       ],
       "accounts": [
         {
@@ -666,6 +390,7 @@ export type Amm = {
         },
         {
           "name": "pool",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -738,7 +463,7 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenProgram"
+                "path": "tokenProgramA"
               },
               {
                 "kind": "account",
@@ -795,7 +520,7 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenProgram"
+                "path": "tokenProgramB"
               },
               {
                 "kind": "account",
@@ -842,258 +567,192 @@ export type Amm = {
           }
         },
         {
-          "name": "userAccountA",
+          "name": "treasuryAccountA",
+          "docs": [
+            "Treasury token account receiving the accrued token A fees"
+          ],
+          "writable": true
+        },
+        {
+          "name": "treasuryAccountB",
+          "docs": [
+            "Treasury token account receiving the accrued token B fees"
+          ],
+          "writable": true
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Solana ecosystem accounts",
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createAmm",
+      "discriminator": [
+        242,
+        91,
+        21,
+        170,
+        5,
+        68,
+        125,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "amm",
           "writable": true,
           "pda": {
             "seeds": [
               {
-                "kind": "account",
-                "path": "user"
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
               },
               {
-                "kind": "account",
-                "path": "tokenProgram"
+                "kind": "arg",
+                "path": "mintA"
               },
               {
-                "kind": "account",
-                "path": "mintA"
+                "kind": "arg",
+                "path": "mintB"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
-          }
-        },
-        {
-          "name": "userAccountB",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "account",
-                "path": "tokenProgram"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "userLpAccount",
-          "writable": true,
+          "name": "config",
+          "docs": [
+            "Program-wide config; only its admin may create AMMs"
+          ],
           "pda": {
             "seeds": [
               {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "account",
-                "path": "tokenProgram"
-              },
-              {
-                "kind": "account",
-                "path": "lpMint"
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "poolLpAccount"
+          "name": "admin",
+          "docs": [
+            "The admin of the AMM, which has to be the config admin"
+          ],
+          "signer": true,
+          "relations": [
+            "config"
+          ]
         },
         {
-          "name": "lpMint",
-          "writable": true
+          "name": "solFeeCollector",
+          "docs": [
+            "SOL fee collector account"
+          ]
         },
         {
-          "name": "user",
+          "name": "authority",
           "writable": true,
           "signer": true
         },
         {
-          "name": "systemProgram",
-          "docs": [
-            "Solana ecosystem accounts"
-          ],
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+          "name": "mintA"
         },
         {
-          "name": "extraAccountMetaListA",
-          "docs": [
-            "Transfer hook accounts for mint A"
-          ]
+          "name": "mintB"
         },
         {
-          "name": "mintTradeCounterA",
-          "writable": true
-        },
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
         {
-          "name": "extraAccountMetaListB",
-          "docs": [
-            "Transfer hook accounts for mint B"
-          ]
+          "name": "mintA",
+          "type": "pubkey"
         },
         {
-          "name": "mintTradeCounterB",
-          "writable": true
+          "name": "mintB",
+          "type": "pubkey"
         },
         {
-          "name": "transferHookProgramA"
+          "name": "solFee",
+          "type": "u64"
         },
         {
-          "name": "transferHookProgramB"
+          "name": "solFeeCollector",
+          "type": {
+            "option": "pubkey"
+          }
         },
         {
-          "name": "instructionsSysvar",
-          "address": "Sysvar1nstructions1111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "amountA",
-          "type": "u64"
+          "name": "minTradeFeeBps",
+          "type": "u16"
         },
         {
-          "name": "amountB",
-          "type": "u64"
+          "name": "maxTradeFeeBps",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "swap",
+      "name": "createPool",
       "discriminator": [
-        248,
-        198,
-        158,
-        145,
-        225,
-        117,
-        135,
-        200
+        233,
+        146,
+        209,
+        142,
+        207,
+        104,
+        64,
+        188
       ],
       "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "The account paying for all rents"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
         {
           "name": "amm",
           "pda": {
@@ -1118,27 +777,286 @@ export type Amm = {
           }
         },
         {
-          "name": "pool",
+          "name": "config",
+          "writable": true,
           "pda": {
             "seeds": [
               {
-                "kind": "account",
-                "path": "amm"
-              },
-              {
-                "kind": "account",
-                "path": "mintA"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
               }
             ]
           }
         },
         {
-          "name": "poolAuthority",
-          "pda": {
+          "name": "poolIndex",
+          "docs": [
+            "Registry entry for this pool, at the next free index"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  105,
+                  110,
+                  100,
+                  101,
+                  120
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "config.pool_count",
+                "account": "ammConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "observations",
+          "docs": [
+            "Price history for TWAP reads"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintLiquidity",
+          "docs": [
+            "LP tokens take the finer of the two mints' decimals"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "docs": [
+            "LP mint program"
+          ],
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "tradeFeeBps",
+          "type": "u16"
+        },
+        {
+          "name": "curveType",
+          "type": {
+            "defined": {
+              "name": "curveType"
+            }
+          }
+        },
+        {
+          "name": "amp",
+          "type": "u64"
+        },
+        {
+          "name": "weightA",
+          "type": "u16"
+        },
+        {
+          "name": "tickSpacing",
+          "type": "u16"
+        },
+        {
+          "name": "sqrtPriceX64",
+          "type": "u128"
+        }
+      ]
+    },
+    {
+      "name": "createPoolTokenAccounts",
+      "discriminator": [
+        121,
+        90,
+        65,
+        202,
+        12,
+        119,
+        182,
+        213
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "The account paying for the transaction"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "amm",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
             "seeds": [
               {
                 "kind": "account",
@@ -1180,6 +1098,9 @@ export type Amm = {
         {
           "name": "mintB"
         },
+        {
+          "name": "lpMint"
+        },
         {
           "name": "poolAccountA",
           "writable": true,
@@ -1191,7 +1112,7 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenProgram"
+                "path": "tokenProgramA"
               },
               {
                 "kind": "account",
@@ -1248,7 +1169,7 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenProgram"
+                "path": "tokenProgramB"
               },
               {
                 "kind": "account",
@@ -1295,13 +1216,13 @@ export type Amm = {
           }
         },
         {
-          "name": "userAccountA",
+          "name": "poolLpAccount",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "account",
-                "path": "user"
+                "path": "poolAuthority"
               },
               {
                 "kind": "account",
@@ -1309,7 +1230,7 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "mintA"
+                "path": "lpMint"
               }
             ],
             "program": {
@@ -1352,27 +1273,143 @@ export type Amm = {
           }
         },
         {
-          "name": "userAccountB",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "account",
-                "path": "user"
-              },
-              {
-                "kind": "account",
-                "path": "tokenProgram"
-              },
-              {
-                "kind": "account",
-                "path": "mintB"
-              }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "decreaseLiquidity",
+      "discriminator": [
+        160,
+        38,
+        208,
+        111,
+        104,
+        91,
+        44,
+        1
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "amm"
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true
+        },
+        {
+          "name": "ownerAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
                 151,
                 37,
                 143,
@@ -1409,86 +1446,111 @@ export type Amm = {
           }
         },
         {
-          "name": "user",
-          "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "docs": [
-            "Solana ecosystem accounts"
-          ],
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+          "name": "ownerAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
         },
         {
-          "name": "extraAccountMetaListA",
+          "name": "tokenProgramA",
           "docs": [
-            "Transfer hook accounts for mint A"
+            "Token program of mint A, either SPL Token or Token-2022"
           ]
         },
         {
-          "name": "mintTradeCounterA",
-          "writable": true
-        },
-        {
-          "name": "extraAccountMetaListB",
+          "name": "tokenProgramB",
           "docs": [
-            "Transfer hook accounts for mint B"
+            "Token program of mint B, either SPL Token or Token-2022"
           ]
-        },
-        {
-          "name": "mintTradeCounterB",
-          "writable": true
-        },
-        {
-          "name": "transferHookProgramA"
-        },
-        {
-          "name": "transferHookProgramB"
-        },
-        {
-          "name": "instructionsSysvar",
-          "address": "Sysvar1nstructions1111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "swapA",
-          "type": "bool"
+          "name": "liquidity",
+          "type": "u128"
         },
         {
-          "name": "inputAmount",
+          "name": "minAmountA",
           "type": "u64"
         },
         {
-          "name": "minOutputAmount",
+          "name": "minAmountB",
           "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
         }
       ]
     },
     {
-      "name": "updateAdmin",
+      "name": "depositLiquidity",
       "discriminator": [
-        161,
-        176,
-        40,
-        213,
-        60,
-        184,
-        179,
-        228
+        245,
+        99,
+        59,
+        25,
+        151,
+        71,
+        233,
+        249
       ],
       "accounts": [
         {
           "name": "amm",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -1511,49 +1573,62 @@ export type Amm = {
           }
         },
         {
-          "name": "mintA"
-        },
-        {
-          "name": "mintB"
-        },
-        {
-          "name": "admin",
-          "signer": true
-        }
-      ],
-      "args": [
-        {
-          "name": "newAdmin",
-          "type": "pubkey"
-        }
-      ]
-    },
-    {
-      "name": "updateFee",
-      "discriminator": [
-        232,
-        253,
-        195,
-        247,
-        148,
-        212,
-        73,
-        222
-      ],
-      "accounts": [
-        {
-          "name": "amm",
+          "name": "pool",
           "writable": true,
           "pda": {
             "seeds": [
               {
-                "kind": "const",
-                "value": [
-                  97,
-                  109,
-                  109
-                ]
-              },
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "observations",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
               {
                 "kind": "account",
                 "path": "mintA"
@@ -1561,6 +1636,25 @@ export type Amm = {
               {
                 "kind": "account",
                 "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
               }
             ]
           }
@@ -1572,216 +1666,5787 @@ export type Amm = {
           "name": "mintB"
         },
         {
-          "name": "admin",
-          "signer": true
-        }
-      ],
-      "args": [
+          "name": "poolAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
         {
-          "name": "newSolFee",
-          "type": "u64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "amm",
-      "discriminator": [
-        143,
-        245,
-        200,
-        17,
-        74,
-        214,
-        196,
-        135
-      ]
-    },
-    {
-      "name": "pool",
-      "discriminator": [
-        241,
-        154,
-        109,
-        4,
-        17,
-        177,
-        109,
-        188
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidFee",
-      "msg": "Invalid fee amount"
-    },
-    {
-      "code": 6001,
-      "name": "invalidAmount",
-      "msg": "Invalid amount"
-    },
-    {
-      "code": 6002,
-      "name": "insufficientLiquidity",
-      "msg": "Insufficient liquidity"
-    },
-    {
-      "code": 6003,
-      "name": "slippageExceeded",
-      "msg": "Slippage exceeded"
-    },
-    {
-      "code": 6004,
-      "name": "unauthorizedAdmin",
-      "msg": "Unauthorized admin"
-    },
-    {
-      "code": 6005,
-      "name": "poolAlreadyExists",
-      "msg": "Pool already exists"
+          "name": "poolAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userLpAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "lpMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "poolLpAccount",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "maxAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountB",
+          "type": "u64"
+        },
+        {
+          "name": "minLpOut",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "executeParamChange",
+      "discriminator": [
+        162,
+        166,
+        56,
+        243,
+        168,
+        135,
+        66,
+        175
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "relations": [
+            "pendingChange"
+          ]
+        },
+        {
+          "name": "pendingChange",
+          "writable": true
+        },
+        {
+          "name": "proposer",
+          "writable": true,
+          "relations": [
+            "pendingChange"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "flashBorrow",
+      "discriminator": [
+        166,
+        221,
+        220,
+        25,
+        61,
+        73,
+        127,
+        240
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "relations": [
+            "pool"
+          ]
+        },
+        {
+          "name": "pool",
+          "writable": true
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true
+        },
+        {
+          "name": "borrowerAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "borrower"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "borrowerAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "borrower"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "borrower",
+          "signer": true
+        },
+        {
+          "name": "instructionsSysvar",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amountA",
+          "type": "u64"
+        },
+        {
+          "name": "amountB",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "flashRepay",
+      "discriminator": [
+        182,
+        143,
+        19,
+        23,
+        39,
+        221,
+        184,
+        78
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "relations": [
+            "pool"
+          ]
+        },
+        {
+          "name": "pool",
+          "writable": true
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true
+        },
+        {
+          "name": "borrowerAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "borrower"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "borrowerAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "borrower"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "borrower",
+          "signer": true
+        },
+        {
+          "name": "instructionsSysvar",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "getTwap",
+      "discriminator": [
+        110,
+        181,
+        179,
+        141,
+        85,
+        10,
+        37,
+        120
+      ],
+      "accounts": [
+        {
+          "name": "pool"
+        },
+        {
+          "name": "observations",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAccountA"
+        },
+        {
+          "name": "poolAccountB"
+        }
+      ],
+      "args": [
+        {
+          "name": "window",
+          "type": "i64"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "twap"
+        }
+      }
+    },
+    {
+      "name": "grantRole",
+      "discriminator": [
+        218,
+        234,
+        128,
+        15,
+        82,
+        33,
+        236,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "role",
+          "type": {
+            "defined": {
+              "name": "role"
+            }
+          }
+        },
+        {
+          "name": "holder",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "increaseLiquidity",
+      "discriminator": [
+        46,
+        156,
+        243,
+        118,
+        13,
+        205,
+        251,
+        178
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "amm"
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "relations": [
+            "position"
+          ]
+        },
+        {
+          "name": "position",
+          "writable": true
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true
+        },
+        {
+          "name": "ownerAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "ownerAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "liquidity",
+          "type": "u128"
+        },
+        {
+          "name": "maxAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountB",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeConfig",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "The admin of the config, which has to be the program's upgrade authority"
+          ],
+          "signer": true
+        },
+        {
+          "name": "program",
+          "address": "H7dswT3BXcCEeVjjLWkfpBP2p5imuJy7Qaq9i5VCpoos"
+        },
+        {
+          "name": "programData"
+        },
+        {
+          "name": "feeCollector"
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializeTickArray",
+      "discriminator": [
+        11,
+        188,
+        193,
+        214,
+        141,
+        91,
+        149,
+        184
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "The account paying for the tick array's rent"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool"
+        },
+        {
+          "name": "tickArray",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  105,
+                  99,
+                  107,
+                  95,
+                  97,
+                  114,
+                  114,
+                  97,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "arg",
+                "path": "startTickIndex"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "startTickIndex",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "makeImmutable",
+      "discriminator": [
+        2,
+        231,
+        207,
+        201,
+        192,
+        150,
+        95,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openPosition",
+      "discriminator": [
+        135,
+        128,
+        47,
+        77,
+        15,
+        152,
+        240,
+        49
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "docs": [
+            "Owner of the new position, who also pays its rent"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool"
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "path": "tickLower"
+              },
+              {
+                "kind": "arg",
+                "path": "tickUpper"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "tickLower",
+          "type": "i32"
+        },
+        {
+          "name": "tickUpper",
+          "type": "i32"
+        }
+      ]
+    },
+    {
+      "name": "pause",
+      "discriminator": [
+        211,
+        22,
+        221,
+        251,
+        74,
+        121,
+        193,
+        47
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "relations": [
+            "pool"
+          ]
+        },
+        {
+          "name": "pool",
+          "docs": [
+            "Pool to pause or unpause; the whole AMM when omitted"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "pauser",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "proposeAdmin",
+      "discriminator": [
+        121,
+        214,
+        199,
+        212,
+        87,
+        39,
+        117,
+        234
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "queueParamChange",
+      "discriminator": [
+        140,
+        242,
+        124,
+        63,
+        143,
+        237,
+        195,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "amm"
+        },
+        {
+          "name": "pendingChange",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "docs": [
+            "Fee manager or admin, depending on the parameter, who also pays the rent"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "change",
+          "type": {
+            "defined": {
+              "name": "paramChange"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "rampAmplification",
+      "discriminator": [
+        156,
+        224,
+        232,
+        242,
+        26,
+        26,
+        159,
+        44
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "targetAmp",
+          "type": "u64"
+        },
+        {
+          "name": "rampEnd",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "revokeRole",
+      "discriminator": [
+        179,
+        232,
+        2,
+        180,
+        48,
+        227,
+        82,
+        7
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "amm"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "role",
+          "type": {
+            "defined": {
+              "name": "role"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "swap",
+      "discriminator": [
+        248,
+        198,
+        158,
+        145,
+        225,
+        117,
+        135,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "observations",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "solFeeCollector",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "swapA",
+          "type": "bool"
+        },
+        {
+          "name": "inputAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minOutputAmount",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "swapRoute",
+      "discriminator": [
+        86,
+        183,
+        163,
+        144,
+        0,
+        50,
+        173,
+        28
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "hops",
+          "type": "u8"
+        },
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "minOutputAmount",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "swapTokensForExactTokens",
+      "discriminator": [
+        55,
+        103,
+        70,
+        78,
+        30,
+        137,
+        102,
+        19
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "observations",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "solFeeCollector",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "swapA",
+          "type": "bool"
+        },
+        {
+          "name": "outputAmount",
+          "type": "u64"
+        },
+        {
+          "name": "maxInputAmount",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "unpause",
+      "discriminator": [
+        169,
+        144,
+        4,
+        38,
+        10,
+        141,
+        188,
+        255
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "writable": true,
+          "relations": [
+            "pool"
+          ]
+        },
+        {
+          "name": "pool",
+          "docs": [
+            "Pool to pause or unpause; the whole AMM when omitted"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "pauser",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateConfigAdmin",
+      "discriminator": [
+        30,
+        254,
+        195,
+        1,
+        169,
+        26,
+        214,
+        39
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "updateFeeCollector",
+      "discriminator": [
+        132,
+        54,
+        153,
+        82,
+        79,
+        118,
+        79,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "feeCollector",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "updateFeeTiers",
+      "discriminator": [
+        196,
+        121,
+        165,
+        164,
+        129,
+        224,
+        44,
+        162
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "signer": true,
+          "relations": [
+            "config"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "feeTiers",
+          "type": {
+            "vec": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdrawLiquidity",
+      "discriminator": [
+        149,
+        158,
+        33,
+        185,
+        47,
+        243,
+        253,
+        31
+      ],
+      "accounts": [
+        {
+          "name": "amm",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "amm"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "observations",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  98,
+                  115,
+                  101,
+                  114,
+                  118,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintA"
+        },
+        {
+          "name": "mintB"
+        },
+        {
+          "name": "poolAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "poolAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "poolAuthority"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountA",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramA"
+              },
+              {
+                "kind": "account",
+                "path": "mintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userAccountB",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgramB"
+              },
+              {
+                "kind": "account",
+                "path": "mintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userLpAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "tokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "lpMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "Solana ecosystem accounts"
+          ],
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "tokenProgramA",
+          "docs": [
+            "Token program of mint A, either SPL Token or Token-2022"
+          ]
+        },
+        {
+          "name": "tokenProgramB",
+          "docs": [
+            "Token program of mint B, either SPL Token or Token-2022"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "lpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountB",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "amm",
+      "discriminator": [
+        143,
+        245,
+        200,
+        17,
+        74,
+        214,
+        196,
+        135
+      ]
+    },
+    {
+      "name": "ammConfig",
+      "discriminator": [
+        218,
+        244,
+        33,
+        104,
+        203,
+        203,
+        43,
+        111
+      ]
+    },
+    {
+      "name": "observations",
+      "discriminator": [
+        119,
+        205,
+        13,
+        6,
+        93,
+        29,
+        178,
+        203
+      ]
+    },
+    {
+      "name": "pendingParamChange",
+      "discriminator": [
+        206,
+        196,
+        38,
+        132,
+        189,
+        11,
+        183,
+        115
+      ]
+    },
+    {
+      "name": "pool",
+      "discriminator": [
+        241,
+        154,
+        109,
+        4,
+        17,
+        177,
+        109,
+        188
+      ]
+    },
+    {
+      "name": "poolIndex",
+      "discriminator": [
+        52,
+        134,
+        192,
+        185,
+        67,
+        48,
+        14,
+        96
+      ]
+    },
+    {
+      "name": "position",
+      "discriminator": [
+        170,
+        188,
+        143,
+        228,
+        122,
+        64,
+        247,
+        208
+      ]
+    },
+    {
+      "name": "tickArray",
+      "discriminator": [
+        69,
+        97,
+        189,
+        190,
+        110,
+        7,
+        66,
+        187
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "adminChanged",
+      "discriminator": [
+        232,
+        34,
+        31,
+        226,
+        62,
+        18,
+        19,
+        114
+      ]
+    },
+    {
+      "name": "adminProposed",
+      "discriminator": [
+        129,
+        249,
+        226,
+        227,
+        199,
+        82,
+        110,
+        243
+      ]
+    },
+    {
+      "name": "ammCreated",
+      "discriminator": [
+        6,
+        184,
+        55,
+        235,
+        147,
+        170,
+        195,
+        252
+      ]
+    },
+    {
+      "name": "ammMadeImmutable",
+      "discriminator": [
+        136,
+        137,
+        59,
+        216,
+        219,
+        250,
+        123,
+        78
+      ]
+    },
+    {
+      "name": "amplificationRampStarted",
+      "discriminator": [
+        34,
+        13,
+        61,
+        105,
+        145,
+        44,
+        102,
+        37
+      ]
+    },
+    {
+      "name": "configUpdated",
+      "discriminator": [
+        40,
+        241,
+        230,
+        122,
+        11,
+        19,
+        198,
+        194
+      ]
+    },
+    {
+      "name": "feeUpdated",
+      "discriminator": [
+        228,
+        75,
+        43,
+        103,
+        9,
+        196,
+        182,
+        4
+      ]
+    },
+    {
+      "name": "flashLoanBorrowed",
+      "discriminator": [
+        37,
+        86,
+        25,
+        222,
+        83,
+        163,
+        251,
+        242
+      ]
+    },
+    {
+      "name": "flashLoanRepaid",
+      "discriminator": [
+        9,
+        204,
+        105,
+        115,
+        70,
+        7,
+        25,
+        198
+      ]
+    },
+    {
+      "name": "liquidityDeposited",
+      "discriminator": [
+        218,
+        155,
+        74,
+        193,
+        59,
+        66,
+        94,
+        122
+      ]
+    },
+    {
+      "name": "liquidityWithdrawn",
+      "discriminator": [
+        240,
+        120,
+        73,
+        139,
+        154,
+        31,
+        218,
+        68
+      ]
+    },
+    {
+      "name": "paramChangeCancelled",
+      "discriminator": [
+        160,
+        193,
+        214,
+        245,
+        184,
+        201,
+        101,
+        136
+      ]
+    },
+    {
+      "name": "paramChangeExecuted",
+      "discriminator": [
+        124,
+        4,
+        197,
+        228,
+        157,
+        80,
+        66,
+        225
+      ]
+    },
+    {
+      "name": "paramChangeQueued",
+      "discriminator": [
+        245,
+        35,
+        159,
+        199,
+        211,
+        51,
+        153,
+        187
+      ]
+    },
+    {
+      "name": "pauseUpdated",
+      "discriminator": [
+        203,
+        203,
+        33,
+        225,
+        130,
+        103,
+        90,
+        105
+      ]
+    },
+    {
+      "name": "poolCreated",
+      "discriminator": [
+        202,
+        44,
+        41,
+        88,
+        104,
+        220,
+        157,
+        82
+      ]
+    },
+    {
+      "name": "poolVaultsCreated",
+      "discriminator": [
+        131,
+        54,
+        234,
+        74,
+        236,
+        25,
+        95,
+        72
+      ]
+    },
+    {
+      "name": "positionClosed",
+      "discriminator": [
+        157,
+        163,
+        227,
+        228,
+        13,
+        97,
+        138,
+        121
+      ]
+    },
+    {
+      "name": "positionFeesCollected",
+      "discriminator": [
+        66,
+        110,
+        48,
+        60,
+        179,
+        236,
+        136,
+        171
+      ]
+    },
+    {
+      "name": "positionLiquidityChanged",
+      "discriminator": [
+        126,
+        129,
+        191,
+        242,
+        166,
+        36,
+        252,
+        240
+      ]
+    },
+    {
+      "name": "positionOpened",
+      "discriminator": [
+        237,
+        175,
+        243,
+        230,
+        147,
+        117,
+        101,
+        121
+      ]
+    },
+    {
+      "name": "protocolFeesCollected",
+      "discriminator": [
+        165,
+        34,
+        125,
+        155,
+        15,
+        86,
+        99,
+        191
+      ]
+    },
+    {
+      "name": "roleChanged",
+      "discriminator": [
+        85,
+        88,
+        130,
+        5,
+        125,
+        143,
+        206,
+        240
+      ]
+    },
+    {
+      "name": "swapped",
+      "discriminator": [
+        217,
+        52,
+        52,
+        83,
+        147,
+        135,
+        96,
+        109
+      ]
+    },
+    {
+      "name": "tickArrayInitialized",
+      "discriminator": [
+        44,
+        123,
+        115,
+        224,
+        221,
+        59,
+        163,
+        215
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidFee",
+      "msg": "Invalid fee amount"
+    },
+    {
+      "code": 6001,
+      "name": "invalidAmount",
+      "msg": "Invalid amount"
+    },
+    {
+      "code": 6002,
+      "name": "insufficientLiquidity",
+      "msg": "Insufficient liquidity"
+    },
+    {
+      "code": 6003,
+      "name": "slippageExceeded",
+      "msg": "Slippage exceeded"
+    },
+    {
+      "code": 6004,
+      "name": "unauthorizedAdmin",
+      "msg": "Unauthorized admin"
+    },
+    {
+      "code": 6005,
+      "name": "poolAlreadyExists",
+      "msg": "Pool already exists"
+    },
+    {
+      "code": 6006,
+      "name": "poolDoesNotExist",
+      "msg": "Pool does not exist"
+    },
+    {
+      "code": 6007,
+      "name": "invalidPool",
+      "msg": "Invalid pool"
+    },
+    {
+      "code": 6008,
+      "name": "invalidMint",
+      "msg": "Invalid mint"
+    },
+    {
+      "code": 6009,
+      "name": "notSigner",
+      "msg": "Not a signer"
+    },
+    {
+      "code": 6010,
+      "name": "invalidPoolAuthority",
+      "msg": "Invalid pool authority"
+    },
+    {
+      "code": 6011,
+      "name": "invalidFeeCollector",
+      "msg": "Invalid SOL fee collector"
+    },
+    {
+      "code": 6012,
+      "name": "invalidRoute",
+      "msg": "Invalid swap route"
+    },
+    {
+      "code": 6013,
+      "name": "deadlineExceeded",
+      "msg": "Transaction deadline has passed"
+    },
+    {
+      "code": 6014,
+      "name": "invalidTokenProgram",
+      "msg": "Invalid token program"
+    },
+    {
+      "code": 6015,
+      "name": "mintsNotSorted",
+      "msg": "Mints must be ordered so that mint_a < mint_b"
+    },
+    {
+      "code": 6016,
+      "name": "invalidFeeTier",
+      "msg": "Trade fee is not one of the configured fee tiers"
+    },
+    {
+      "code": 6017,
+      "name": "insufficientPriceHistory",
+      "msg": "Not enough price history for the requested TWAP window"
+    },
+    {
+      "code": 6018,
+      "name": "invalidAmplification",
+      "msg": "Invalid amplification coefficient or ramp"
+    },
+    {
+      "code": 6019,
+      "name": "invalidCurve",
+      "msg": "Operation not supported by the pool's curve"
+    },
+    {
+      "code": 6020,
+      "name": "invalidWeights",
+      "msg": "Invalid pool weights"
+    },
+    {
+      "code": 6021,
+      "name": "invalidTickRange",
+      "msg": "Invalid tick, tick range or tick spacing"
+    },
+    {
+      "code": 6022,
+      "name": "invalidSqrtPrice",
+      "msg": "Invalid sqrt price"
+    },
+    {
+      "code": 6023,
+      "name": "invalidTickArray",
+      "msg": "Invalid tick array"
+    },
+    {
+      "code": 6024,
+      "name": "tickArrayMissing",
+      "msg": "A tick array the instruction needs was not passed"
+    },
+    {
+      "code": 6025,
+      "name": "positionNotEmpty",
+      "msg": "Position still holds liquidity or uncollected fees"
+    },
+    {
+      "code": 6026,
+      "name": "invalidPositionOwner",
+      "msg": "Signer does not own the position"
+    },
+    {
+      "code": 6027,
+      "name": "poolLocked",
+      "msg": "Pool is locked by an outstanding flash loan"
+    },
+    {
+      "code": 6028,
+      "name": "flashLoanNotRepaid",
+      "msg": "Flash loan is not repaid later in the transaction"
+    },
+    {
+      "code": 6029,
+      "name": "noFlashLoan",
+      "msg": "No flash loan is outstanding on the pool"
+    },
+    {
+      "code": 6030,
+      "name": "paused",
+      "msg": "The AMM or pool is paused"
+    },
+    {
+      "code": 6031,
+      "name": "unauthorizedPauser",
+      "msg": "Signer is not the pauser"
+    },
+    {
+      "code": 6032,
+      "name": "notPendingAdmin",
+      "msg": "Signer is not the pending admin"
+    },
+    {
+      "code": 6033,
+      "name": "unauthorizedFeeManager",
+      "msg": "Signer is not the fee manager"
+    },
+    {
+      "code": 6034,
+      "name": "invalidTreasury",
+      "msg": "Token account is not owned by the treasury"
+    },
+    {
+      "code": 6035,
+      "name": "invalidParamChange",
+      "msg": "Pending parameter change does not belong to this AMM"
+    },
+    {
+      "code": 6036,
+      "name": "timelockNotElapsed",
+      "msg": "The parameter change delay has not elapsed yet"
+    }
+  ],
+  "types": [
+    {
+      "name": "adminChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "oldAdmin",
+            "type": "pubkey"
+          },
+          {
+            "name": "newAdmin",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "adminProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "The admin making the proposal"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "The proposed admin; default when a proposal is withdrawn"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "amm",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "poolId",
+            "docs": [
+              "The unique pool identifier (fixed size array)"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "Account that has admin authority over the AMM"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "Proposed next admin, who takes over once they accept; default when none is pending"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "solFee",
+            "docs": [
+              "The SOL fee taken on each trade, in lamports (e.g., 0.05 SOL = 50,000,000 lamports)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "solFeeCollector",
+            "docs": [
+              "SOL fee collector account"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "created",
+            "docs": [
+              "The AMM has been created"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "isImmutable",
+            "docs": [
+              "Whether the AMM is immutable"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "minTradeFeeBps",
+            "docs": [
+              "Lowest LP trading fee a pool may be created with, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxTradeFeeBps",
+            "docs": [
+              "Highest LP trading fee a pool may be created with, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "protocolFeeShareBps",
+            "docs": [
+              "Share of every trade fee kept for the protocol, in basis points of the fee"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "flashLoanFeeBps",
+            "docs": [
+              "Fee on flash loans from the AMM's pools, in basis points of the amount borrowed"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "roles",
+            "docs": [
+              "Keys holding the AMM's delegated roles"
+            ],
+            "type": {
+              "defined": {
+                "name": "ammRoles"
+              }
+            }
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Whether trading and new liquidity are stopped in every pool of the AMM"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammConfig",
+      "docs": [
+        "Program-wide settings, one per deployment"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Account that has admin authority over the config"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeCollector",
+            "docs": [
+              "Default collector for fees taken by the protocol"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeTiers",
+            "docs": [
+              "LP trading fees pools may be created with, in basis points; only the first `fee_tier_count` are set"
+            ],
+            "type": {
+              "array": [
+                "u16",
+                8
+              ]
+            }
+          },
+          {
+            "name": "feeTierCount",
+            "docs": [
+              "Number of entries of `fee_tiers` in use"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "poolCount",
+            "docs": [
+              "Number of pools created so far, which is also the index of the next `PoolIndex`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Config PDA bump"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "docs": [
+              "The new AMM"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "mintA",
+            "type": "pubkey"
+          },
+          {
+            "name": "mintB",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "Admin of the AMM, initially holding every role"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "solFee",
+            "docs": [
+              "SOL fee charged per swap, in lamports"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "solFeeCollector",
+            "docs": [
+              "Account the SOL fee is sent to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "minTradeFeeBps",
+            "docs": [
+              "Lowest LP trading fee pools may be created with, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxTradeFeeBps",
+            "docs": [
+              "Highest LP trading fee pools may be created with, in basis points"
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammMadeImmutable",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "The admin who made the AMM immutable"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammRoles",
+      "docs": [
+        "Keys an AMM's admin delegates narrower powers to; each falls back to the admin when revoked"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feeManager",
+            "docs": [
+              "May change the AMM's fees within the bounds set by the admin"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pauser",
+            "docs": [
+              "May pause and unpause the AMM and its pools"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "treasury",
+            "docs": [
+              "Owner of the token accounts protocol fees are collected to; never signs anything"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "amplificationRampStarted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "ampInitial",
+            "type": "u64"
+          },
+          {
+            "name": "ampTarget",
+            "type": "u64"
+          },
+          {
+            "name": "rampStart",
+            "docs": [
+              "Unix timestamps the ramp runs between"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "rampEnd",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "configUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "config",
+            "docs": [
+              "The program-wide config"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "Admin of the config"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeCollector",
+            "docs": [
+              "Default collector for fees taken by the protocol"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeTiers",
+            "docs": [
+              "LP trading fees pools may be created with, in basis points"
+            ],
+            "type": {
+              "vec": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "curveType",
+      "docs": [
+        "Invariant a pool prices swaps with"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "constantProduct"
+          },
+          {
+            "name": "stableSwap"
+          },
+          {
+            "name": "weighted"
+          },
+          {
+            "name": "concentrated"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeUpdated",
+      "docs": [
+        "Fee settings of an AMM after an executed parameter change"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "solFee",
+            "docs": [
+              "SOL fee charged per swap, in lamports"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "solFeeCollector",
+            "docs": [
+              "Account the SOL fee is sent to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "minTradeFeeBps",
+            "docs": [
+              "Lowest LP trading fee new pools may be created with, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "maxTradeFeeBps",
+            "docs": [
+              "Highest LP trading fee new pools may be created with, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "protocolFeeShareBps",
+            "docs": [
+              "Protocol's share of the trade fee, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "flashLoanFeeBps",
+            "docs": [
+              "Fee on flash loans, in basis points of the amount borrowed"
+            ],
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "flashLoanBorrowed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "borrower",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "flashLoanRepaid",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "borrower",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "docs": [
+              "Principal returned"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          },
+          {
+            "name": "feeA",
+            "docs": [
+              "Flash loan fee paid on top"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "docs": [
+              "The depositor"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "docs": [
+              "Amount of A sent by the depositor, including any transfer fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "docs": [
+              "Amount of B sent by the depositor, including any transfer fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "docs": [
+              "LP tokens minted to the depositor, net of the liquidity locked on a first deposit"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLiquidity",
+            "docs": [
+              "LP supply after the deposit, including locked liquidity"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "docs": [
+              "The withdrawer"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "docs": [
+              "Amount of A sent out of the pool"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "docs": [
+              "Amount of B sent out of the pool"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "docs": [
+              "LP tokens burned"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLiquidity",
+            "docs": [
+              "LP supply after the withdrawal"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "observation",
+      "docs": [
+        "Snapshot of a pool's price accumulators"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "timestamp",
+            "docs": [
+              "Unix timestamp of the snapshot"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "priceACumulative",
+            "docs": [
+              "`Pool::price_a_cumulative` at `timestamp`"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "priceBCumulative",
+            "docs": [
+              "`Pool::price_b_cumulative` at `timestamp`"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "observations",
+      "docs": [
+        "Ring buffer of accumulator snapshots for one pool, written at most once per `OBSERVATION_INTERVAL`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "The pool observed"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "index",
+            "docs": [
+              "Slot of the most recent observation"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "count",
+            "docs": [
+              "Number of slots written so far, up to `OBSERVATION_CAPACITY`"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "observations",
+            "docs": [
+              "The snapshots, oldest overwritten first"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "observation"
+                  }
+                },
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "paramChange",
+      "docs": [
+        "AMM parameter change that only takes effect after `PARAM_CHANGE_DELAY`"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "solFee",
+            "fields": [
+              "u64"
+            ]
+          },
+          {
+            "name": "solFeeCollector",
+            "fields": [
+              "pubkey"
+            ]
+          },
+          {
+            "name": "tradeFeeBounds",
+            "fields": [
+              {
+                "name": "minBps",
+                "type": "u16"
+              },
+              {
+                "name": "maxBps",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "protocolFeeShare",
+            "fields": [
+              "u16"
+            ]
+          },
+          {
+            "name": "flashLoanFee",
+            "fields": [
+              "u16"
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "paramChangeCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "docs": [
+              "The AMM the change would have applied to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "The pending change account, closed by the cancellation"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "docs": [
+              "The value that will not take effect"
+            ],
+            "type": {
+              "defined": {
+                "name": "paramChange"
+              }
+            }
+          },
+          {
+            "name": "cancelledBy",
+            "docs": [
+              "Account that cancelled the change"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "paramChangeExecuted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "docs": [
+              "The AMM the change was applied to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "The pending change account, closed by the execution"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "docs": [
+              "The value now in effect"
+            ],
+            "type": {
+              "defined": {
+                "name": "paramChange"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "paramChangeQueued",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "docs": [
+              "The AMM the change applies to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "The pending change account"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "docs": [
+              "The new value"
+            ],
+            "type": {
+              "defined": {
+                "name": "paramChange"
+              }
+            }
+          },
+          {
+            "name": "eta",
+            "docs": [
+              "Earliest time the change can be executed, as a unix timestamp"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "proposer",
+            "docs": [
+              "Account that queued the change"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pauseUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "docs": [
+              "The pool paused or unpaused; none when the switch is the AMM-wide one"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "paused",
+            "type": "bool"
+          },
+          {
+            "name": "pauser",
+            "docs": [
+              "The pauser who flipped the switch"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingParamChange",
+      "docs": [
+        "A queued `ParamChange`, one per AMM and parameter"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "docs": [
+              "The AMM the change applies to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "change",
+            "docs": [
+              "The new value"
+            ],
+            "type": {
+              "defined": {
+                "name": "paramChange"
+              }
+            }
+          },
+          {
+            "name": "eta",
+            "docs": [
+              "Earliest time the change can be executed, as a unix timestamp"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "proposer",
+            "docs": [
+              "Account that queued the change and gets the rent back"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pool",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "mintA",
+            "docs": [
+              "Mint of token A"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "mintB",
+            "docs": [
+              "Mint of token B"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "vaultA",
+            "docs": [
+              "Token A vault account"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "vaultB",
+            "docs": [
+              "Token B vault account"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpMint",
+            "docs": [
+              "LP token mint"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "totalLiquidity",
+            "docs": [
+              "Total liquidity in the pool"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "poolAuthorityBump",
+            "docs": [
+              "Pool authority bump for deterministic derivation"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "tradeFeeBps",
+            "docs": [
+              "LP trading fee taken from the input of every swap, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "protocolFeesA",
+            "docs": [
+              "Protocol fees accrued in vault A and not yet collected"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFeesB",
+            "docs": [
+              "Protocol fees accrued in vault B and not yet collected"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tokenProgramA",
+            "docs": [
+              "Token program that owns mint A (SPL Token or Token-2022)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenProgramB",
+            "docs": [
+              "Token program that owns mint B (SPL Token or Token-2022)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "priceACumulative",
+            "docs": [
+              "Time-weighted sum of the price of A in B (reserve_b / reserve_a) as Q64.64, wrapping"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "priceBCumulative",
+            "docs": [
+              "Time-weighted sum of the price of B in A (reserve_a / reserve_b) as Q64.64, wrapping"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "lastUpdateTimestamp",
+            "docs": [
+              "Unix timestamp the price accumulators were last brought up to"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "curveType",
+            "docs": [
+              "Invariant used to price swaps"
+            ],
+            "type": {
+              "defined": {
+                "name": "curveType"
+              }
+            }
+          },
+          {
+            "name": "ampInitial",
+            "docs": [
+              "StableSwap amplification at `amp_ramp_start`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ampTarget",
+            "docs": [
+              "StableSwap amplification from `amp_ramp_end` on"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ampRampStart",
+            "docs": [
+              "Unix timestamp the current amplification ramp started"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "ampRampEnd",
+            "docs": [
+              "Unix timestamp the current amplification ramp ends"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "weightA",
+            "docs": [
+              "Weighted pools: weight of token A, in basis points of FEE_BPS_DENOMINATOR"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "weightB",
+            "docs": [
+              "Weighted pools: weight of token B, in basis points of FEE_BPS_DENOMINATOR"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "tickSpacing",
+            "docs": [
+              "Concentrated pools: distance between initializable ticks"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "tickCurrent",
+            "docs": [
+              "Concentrated pools: tick of the current price, i.e. the largest tick at or below `sqrt_price_x64`"
+            ],
+            "type": "i32"
+          },
+          {
+            "name": "sqrtPriceX64",
+            "docs": [
+              "Concentrated pools: square root of the price of A in B, Q64.64"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "liquidity",
+            "docs": [
+              "Concentrated pools: liquidity of the positions whose range contains the current price"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeGrowthGlobalAX64",
+            "docs": [
+              "Concentrated pools: LP fees in A earned per unit of liquidity since creation, Q64.64, wrapping"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeGrowthGlobalBX64",
+            "docs": [
+              "Concentrated pools: LP fees in B earned per unit of liquidity since creation, Q64.64, wrapping"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "locked",
+            "docs": [
+              "Set while a flash loan is outstanding; swaps and liquidity changes are refused until it is repaid"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "flashLoanAmountA",
+            "docs": [
+              "Principal of the outstanding flash loan in A"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flashLoanAmountB",
+            "docs": [
+              "Principal of the outstanding flash loan in B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Whether trading and new liquidity are stopped in this pool; withdrawals stay open"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "mintA",
+            "type": "pubkey"
+          },
+          {
+            "name": "mintB",
+            "type": "pubkey"
+          },
+          {
+            "name": "lpMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "index",
+            "docs": [
+              "Position of the pool in the on-chain registry"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "curveType",
+            "type": {
+              "defined": {
+                "name": "curveType"
+              }
+            }
+          },
+          {
+            "name": "tradeFeeBps",
+            "docs": [
+              "LP trading fee, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "amp",
+            "docs": [
+              "StableSwap amplification coefficient, zero on other curves"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "weightA",
+            "docs": [
+              "Weighted pool weights in basis points, zero on other curves"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "weightB",
+            "type": "u16"
+          },
+          {
+            "name": "tickSpacing",
+            "docs": [
+              "Concentrated pool tick spacing and initial price, zero on other curves"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "sqrtPriceX64",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolIndex",
+      "docs": [
+        "Registry entry for one pool, seeded by its creation order so every pool can be",
+        "listed by walking indexes 0..`AmmConfig::pool_count`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "index",
+            "docs": [
+              "Position of the pool in the registry"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pool",
+            "docs": [
+              "The pool"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amm",
+            "docs": [
+              "AMM of the pool"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "mintA",
+            "docs": [
+              "Mint of token A"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "mintB",
+            "docs": [
+              "Mint of token B"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolVaultsCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "vaultA",
+            "type": "pubkey"
+          },
+          {
+            "name": "vaultB",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "position",
+      "docs": [
+        "Liquidity one owner provides to a concentrated pool between two ticks"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "The pool"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "Account allowed to change the position and collect its fees"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tickLower",
+            "docs": [
+              "Lower end of the range, inclusive"
+            ],
+            "type": "i32"
+          },
+          {
+            "name": "tickUpper",
+            "docs": [
+              "Upper end of the range, exclusive"
+            ],
+            "type": "i32"
+          },
+          {
+            "name": "liquidity",
+            "docs": [
+              "Liquidity the position provides while the price is inside its range"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeGrowthInsideALastX64",
+            "docs": [
+              "Fee growth in A inside the range when the position was last updated, Q64.64"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feeGrowthInsideBLastX64",
+            "docs": [
+              "Fee growth in B inside the range when the position was last updated, Q64.64"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "feesOwedA",
+            "docs": [
+              "LP fees in A earned and not yet collected"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feesOwedB",
+            "docs": [
+              "LP fees in B earned and not yet collected"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Position PDA bump"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "position",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionFeesCollected",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "position",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionLiquidityChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "position",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "liquidityDelta",
+            "docs": [
+              "Liquidity added (positive) or removed (negative)"
+            ],
+            "type": "i128"
+          },
+          {
+            "name": "amountA",
+            "docs": [
+              "Amount of A that entered or left the vault, excluding any transfer fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "docs": [
+              "Amount of B that entered or left the vault, excluding any transfer fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "liquidity",
+            "docs": [
+              "Liquidity of the position afterwards"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "poolDoesNotExist",
-      "msg": "Pool does not exist"
+      "name": "positionOpened",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "position",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "tickLower",
+            "type": "i32"
+          },
+          {
+            "name": "tickUpper",
+            "type": "i32"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "invalidPool",
-      "msg": "Invalid pool"
+      "name": "protocolFeesCollected",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          },
+          {
+            "name": "treasuryAccountA",
+            "docs": [
+              "Treasury token accounts the fees were sent to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "treasuryAccountB",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "invalidMint",
-      "msg": "Invalid mint"
+      "name": "role",
+      "docs": [
+        "Delegated role on an AMM, see `AmmRoles`"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "feeManager"
+          },
+          {
+            "name": "pauser"
+          },
+          {
+            "name": "treasury"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "notSigner",
-      "msg": "Not a signer"
+      "name": "roleChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amm",
+            "type": "pubkey"
+          },
+          {
+            "name": "role",
+            "type": {
+              "defined": {
+                "name": "role"
+              }
+            }
+          },
+          {
+            "name": "holder",
+            "docs": [
+              "New holder of the role; the admin when it was revoked"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "invalidPoolAuthority",
-      "msg": "Invalid pool authority"
-    }
-  ],
-  "types": [
-    {
-      "name": "amm",
+      "name": "swapped",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "poolId",
+            "name": "amm",
             "docs": [
-              "The unique pool identifier (fixed size array)"
+              "The AMM the pool belongs to"
             ],
-            "type": {
-              "array": [
-                "u8",
-                64
-              ]
-            }
+            "type": "pubkey"
           },
           {
-            "name": "admin",
+            "name": "pool",
             "docs": [
-              "Account that has admin authority over the AMM"
+              "The pool that was traded against"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "docs": [
+              "The trader"
             ],
             "type": "pubkey"
           },
+          {
+            "name": "swapA",
+            "docs": [
+              "true for A to B, false for B to A"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "inputAmount",
+            "docs": [
+              "Amount of the input token sent by the user"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "outputAmount",
+            "docs": [
+              "Amount of the output token received by the user"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "inputTransferFee",
+            "docs": [
+              "Token-2022 transfer fee withheld from the input on its way into the pool"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "outputTransferFee",
+            "docs": [
+              "Token-2022 transfer fee withheld from the output on its way to the user"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tradeFee",
+            "docs": [
+              "LP trading fee taken from the input, in input token units"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "docs": [
+              "Part of the trade fee set aside for the protocol, in input token units"
+            ],
+            "type": "u64"
+          },
           {
             "name": "solFee",
             "docs": [
-              "The SOL fee taken on each trade, in lamports (e.g., 0.05 SOL = 50,000,000 lamports)"
+              "SOL fee charged to the user, in lamports"
             ],
             "type": "u64"
           },
           {
             "name": "solFeeCollector",
             "docs": [
-              "SOL fee collector account"
+              "Account the SOL fee was sent to"
             ],
             "type": "pubkey"
           },
           {
-            "name": "created",
+            "name": "reserveABefore",
             "docs": [
-              "The AMM has been created"
+              "Pool reserves of A before the swap, excluding uncollected protocol fees"
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "isImmutable",
+            "name": "reserveBBefore",
             "docs": [
-              "Whether the AMM is immutable"
+              "Pool reserves of B before the swap, excluding uncollected protocol fees"
             ],
-            "type": "bool"
+            "type": "u64"
+          },
+          {
+            "name": "reserveAAfter",
+            "docs": [
+              "Pool reserves of A after the swap, excluding uncollected protocol fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserveBAfter",
+            "docs": [
+              "Pool reserves of B after the swap, excluding uncollected protocol fees"
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "pool",
+      "name": "tick",
+      "docs": [
+        "One initializable tick of a concentrated pool"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "amm",
-            "type": "pubkey"
+            "name": "liquidityNet",
+            "docs": [
+              "Liquidity added to the pool when the price crosses the tick upwards, removed when it crosses downwards"
+            ],
+            "type": "i128"
           },
           {
-            "name": "mintA",
+            "name": "liquidityGross",
             "docs": [
-              "Mint of token A"
+              "Liquidity of all positions bounded by the tick; zero means the tick is uninitialized"
             ],
-            "type": "pubkey"
+            "type": "u128"
           },
           {
-            "name": "mintB",
+            "name": "feeGrowthOutsideAX64",
             "docs": [
-              "Mint of token B"
+              "LP fee growth in A on the other side of the tick from the current price, Q64.64"
             ],
-            "type": "pubkey"
+            "type": "u128"
           },
           {
-            "name": "vaultA",
+            "name": "feeGrowthOutsideBX64",
             "docs": [
-              "Token A vault account"
+              "LP fee growth in B on the other side of the tick from the current price, Q64.64"
+            ],
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "tickArray",
+      "docs": [
+        "`TICK_ARRAY_SIZE` consecutive initializable ticks of a concentrated pool, starting",
+        "at a multiple of `TICK_ARRAY_SIZE * tick_spacing`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "The pool the ticks belong to"
             ],
             "type": "pubkey"
           },
           {
-            "name": "vaultB",
+            "name": "startTickIndex",
             "docs": [
-              "Token B vault account"
+              "Tick index of `ticks[0]`"
             ],
-            "type": "pubkey"
+            "type": "i32"
           },
           {
-            "name": "lpMint",
+            "name": "ticks",
             "docs": [
-              "LP token mint"
+              "The ticks, `tick_spacing` apart"
             ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "tick"
+                  }
+                },
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "tickArrayInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
             "type": "pubkey"
           },
           {
-            "name": "totalLiquidity",
+            "name": "tickArray",
+            "type": "pubkey"
+          },
+          {
+            "name": "startTickIndex",
+            "type": "i32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "twap",
+      "docs": [
+        "Time-weighted average prices returned by `get_twap`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "priceA",
             "docs": [
-              "Total liquidity in the pool"
+              "Average price of A in B over the window, Q64.64"
             ],
-            "type": "u64"
+            "type": "u128"
           },
           {
-            "name": "poolAuthorityBump",
+            "name": "priceB",
             "docs": [
-              "Pool authority bump for deterministic derivation"
+              "Average price of B in A over the window, Q64.64"
             ],
-            "type": "u8"
+            "type": "u128"
+          },
+          {
+            "name": "window",
+            "docs": [
+              "Seconds actually covered, at least the requested window"
+            ],
+            "type": "i64"
           }
         ]
       }
@@ -15,6 +15,39 @@ import {
 } from '../config/constants'
 import { COUNTER_HOOK_PROGRAM_ID } from '../config/program'
 
+// Curve a pool is created with; fields a curve doesn't use are ignored by the program
+export type PoolCurve = {
+  curveType: { constantProduct: {} } | { stableSwap: {} } | { weighted: {} } | { concentrated: {} }
+  amp: number
+  weightA: number
+  tickSpacing: number
+  sqrtPriceX64: BN
+}
+
+// AMMs and pools only exist for mints in canonical (byte) order, as the program's sort_mints
+export function sortMints(x: web3.PublicKey, y: web3.PublicKey): [web3.PublicKey, web3.PublicKey] {
+  return Buffer.compare(x.toBuffer(), y.toBuffer()) < 0 ? [x, y] : [y, x]
+}
+
+const INSTRUCTIONS_SYSVAR = new web3.PublicKey('Sysvar1nstructions1111111111111111111111111')
+
+// Transfer hook accounts go in remaining accounts, where the program finds each one by key,
+// so an account both mints share is passed once
+function hookRemainingAccounts(writable: web3.PublicKey[], readonly: web3.PublicKey[]): web3.AccountMeta[] {
+  const metas = new Map<string, web3.AccountMeta>()
+  for (const pubkey of readonly) metas.set(pubkey.toBase58(), { pubkey, isSigner: false, isWritable: false })
+  for (const pubkey of writable) metas.set(pubkey.toBase58(), { pubkey, isSigner: false, isWritable: true })
+  return Array.from(metas.values())
+}
+
+export const CONSTANT_PRODUCT_CURVE: PoolCurve = {
+  curveType: { constantProduct: {} },
+  amp: 0,
+  weightA: 0,
+  tickSpacing: 0,
+  sqrtPriceX64: new BN(0),
+}
+
 export class AnchorClient {
   private connection: Connection
   private program: Program
@@ -73,15 +106,20 @@ export class AnchorClient {
     mintB: web3.PublicKey,
     solFeeLamports: number,
     solFeeCollector: web3.PublicKey,
+    minTradeFeeBps: number,
+    maxTradeFeeBps: number,
     signTransaction: (t: Transaction) => Promise<Transaction>
   ) {
     const [amm] = web3.PublicKey.findProgramAddressSync([
       Buffer.from('amm'), mintA.toBuffer(), mintB.toBuffer()
     ], this.program.programId)
+    // Only the config admin may create AMMs, within the config's fee tiers
+    const [config] = web3.PublicKey.findProgramAddressSync([Buffer.from('amm_config')], this.program.programId)
     const tx = await this.program.methods
-      .createAmm(mintA, mintB, new BN(solFeeLamports), solFeeCollector)
+      .createAmm(mintA, mintB, new BN(solFeeLamports), solFeeCollector, minTradeFeeBps, maxTradeFeeBps)
       .accounts({
         amm,
+        config,
         admin: this.provider.wallet.publicKey,
         solFeeCollector,
         authority: this.provider.wallet.publicKey,
@@ -98,19 +136,27 @@ export class AnchorClient {
   async createPool(
     mintA: web3.PublicKey,
     mintB: web3.PublicKey,
+    tradeFeeBps: number,
+    curve: PoolCurve,
     signTransaction: (t: Transaction) => Promise<Transaction>
   ) {
     const [amm] = web3.PublicKey.findProgramAddressSync([Buffer.from('amm'), mintA.toBuffer(), mintB.toBuffer()], this.program.programId)
     const [pool] = web3.PublicKey.findProgramAddressSync([amm.toBuffer(), mintA.toBuffer(), mintB.toBuffer()], this.program.programId)
     const [poolAuthority] = web3.PublicKey.findProgramAddressSync([pool.toBuffer(), mintA.toBuffer(), mintB.toBuffer(), Buffer.from(POOL_AUTHORITY_SEED)], this.program.programId)
+    // Every pool is registered in the config under the next free index
+    const [config] = web3.PublicKey.findProgramAddressSync([Buffer.from('amm_config')], this.program.programId)
+    const configState = await (this.program.account as any).ammConfig.fetch(config)
+    const [poolIndex] = web3.PublicKey.findProgramAddressSync([Buffer.from('pool_index'), new BN(configState.poolCount).toArrayLike(Buffer, 'le', 8)], this.program.programId)
     const lpKeypair = web3.Keypair.generate()
     const lpPubkey = lpKeypair.publicKey
 
     const tx = await this.program.methods
-      .createPool()
+      .createPool(tradeFeeBps, curve.curveType, new BN(curve.amp), curve.weightA, curve.tickSpacing, curve.sqrtPriceX64)
       .accounts({
         payer: this.provider.wallet.publicKey,
         amm,
+        config,
+        poolIndex,
         pool,
         poolAuthority,
         mintLiquidity: lpPubkey,
@@ -119,6 +165,9 @@ export class AnchorClient {
         systemProgram: web3.SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
         tokenProgram: TOKEN_2022_PROGRAM,
+        // Both mints the app creates are Token-2022
+        tokenProgramA: TOKEN_2022_PROGRAM,
+        tokenProgramB: TOKEN_2022_PROGRAM,
       })
       .signers([lpKeypair])
       .transaction()
@@ -161,6 +210,8 @@ export class AnchorClient {
         systemProgram: web3.SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
         tokenProgram: TOKEN_2022_PROGRAM,
+        tokenProgramA: TOKEN_2022_PROGRAM,
+        tokenProgramB: TOKEN_2022_PROGRAM,
       })
       .transaction()
 
@@ -173,8 +224,10 @@ export class AnchorClient {
     mintB: web3.PublicKey,
     pool: web3.PublicKey,
     lpMint: web3.PublicKey,
-    amountA: number,
-    amountB: number,
+    maxAmountA: number,
+    maxAmountB: number,
+    minLpOut: number,
+    deadline: number | null,
     transferHookProgramId: web3.PublicKey,
     signTransaction: (t: Transaction) => Promise<Transaction>
   ) {
@@ -225,7 +278,12 @@ export class AnchorClient {
     )
 
     const tx = await this.program.methods
-      .depositLiquidity(new BN(amountA), new BN(amountB))
+      .depositLiquidity(
+        new BN(maxAmountA),
+        new BN(maxAmountB),
+        new BN(minLpOut),
+        deadline === null ? null : new BN(deadline)
+      )
       .accounts({
         amm,
         pool,
@@ -243,14 +301,13 @@ export class AnchorClient {
         systemProgram: web3.SystemProgram.programId,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
         tokenProgram: TOKEN_2022_PROGRAM,
-        extraAccountMetaListA,
-        mintTradeCounterA,
-        extraAccountMetaListB,
-        mintTradeCounterB,
-        transferHookProgramA: transferHookProgramId,
-        transferHookProgramB: transferHookProgramId,
-        instructionsSysvar: new web3.PublicKey('Sysvar1nstructions1111111111111111111111111'),
+        tokenProgramA: TOKEN_2022_PROGRAM,
+        tokenProgramB: TOKEN_2022_PROGRAM,
       })
+      .remainingAccounts(hookRemainingAccounts(
+        [mintTradeCounterA, mintTradeCounterB],
+        [transferHookProgramId, extraAccountMetaListA, extraAccountMetaListB, INSTRUCTIONS_SYSVAR]
+      ))
       .transaction()
 
     console.log('[depositLiquidity] sending transaction...')
@@ -265,6 +322,7 @@ export class AnchorClient {
     swapA: boolean,
     inputAmount: number,
     minOutputAmount: number,
+    deadline: number | null,
     transferHookProgramIdA: web3.PublicKey,
     transferHookProgramIdB: web3.PublicKey,
     signTransaction: (transaction: Transaction) => Promise<Transaction>
@@ -299,6 +357,10 @@ export class AnchorClient {
       console.log('User Account A:', userAccountA.toString())
       console.log('User Account B:', userAccountB.toString())
 
+      // The SOL fee goes to the collector recorded on the AMM
+      const ammState = await (this.program.account as any).amm.fetch(ammId)
+      const solFeeCollector = new web3.PublicKey(ammState.solFeeCollector ?? ammState.sol_fee_collector)
+
       // Derive transfer hook accounts using kit PDA helper
       const COUNTER_HOOK_PROGRAM = new web3.PublicKey(COUNTER_HOOK_PROGRAM_ID)
       const [extraAccountMetaListA] = web3.PublicKey.findProgramAddressSync([
@@ -323,7 +385,7 @@ export class AnchorClient {
 
       // Use Anchor's generated instruction with proper account mapping
       const tx = await this.program.methods
-        .swap(swapA, new BN(inputAmount), new BN(minOutputAmount))
+        .swap(swapA, new BN(inputAmount), new BN(minOutputAmount), deadline === null ? null : new BN(deadline))
         .accounts({
           amm: ammId,
           pool: poolAddress,
@@ -335,17 +397,16 @@ export class AnchorClient {
           userAccountA,
           userAccountB,
           user: this.provider.wallet.publicKey,
+          solFeeCollector,
           systemProgram: web3.SystemProgram.programId,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM,
-          tokenProgram: TOKEN_2022_PROGRAM,
-          extraAccountMetaListA,
-          mintTradeCounterA,
-          extraAccountMetaListB,
-          mintTradeCounterB,
-          transferHookProgramA: transferHookProgramIdA,
-          transferHookProgramB: transferHookProgramIdB,
-          instructionsSysvar: new web3.PublicKey('Sysvar1nstructions1111111111111111111111111'),
+          tokenProgramA: TOKEN_2022_PROGRAM,
+          tokenProgramB: TOKEN_2022_PROGRAM,
         })
+        .remainingAccounts(hookRemainingAccounts(
+          [mintTradeCounterA, mintTradeCounterB],
+          [transferHookProgramIdA, transferHookProgramIdB, extraAccountMetaListA, extraAccountMetaListB, INSTRUCTIONS_SYSVAR]
+        ))
         .transaction()
 
       // Sign and send transaction using Anchor's provider
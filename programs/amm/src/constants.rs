@@ -3,4 +3,32 @@ pub const POOL_SEED: &[u8] = b"pool";
 pub const VAULT_A_SEED: &[u8] = b"vault_a";
 pub const VAULT_B_SEED: &[u8] = b"vault_b";
 pub const LP_MINT_SEED: &[u8] = b"lp_mint";
-pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority"; 
\ No newline at end of file
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority"; 
+pub const FEE_BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_TRADE_FEE_BPS: u16 = 1_000; // 10%
+pub const MAX_ROUTE_HOPS: usize = 4;
+pub const AMM_CONFIG_SEED: &[u8] = b"amm_config";
+pub const POOL_INDEX_SEED: &[u8] = b"pool_index";
+pub const MAX_FEE_TIERS: usize = 8;
+pub const OBSERVATIONS_SEED: &[u8] = b"observations";
+pub const OBSERVATION_CAPACITY: usize = 32;
+pub const OBSERVATION_INTERVAL: i64 = 300; // 5 minutes between observations, so the ring spans at least ~2.6 hours
+pub const MIN_AMP: u64 = 1;
+pub const MAX_AMP: u64 = 1_000_000;
+pub const MAX_AMP_CHANGE: u64 = 10; // per ramp, up or down
+pub const MIN_AMP_RAMP_DURATION: i64 = 86_400; // 1 day
+pub const STABLE_SWAP_MAX_ITERATIONS: usize = 255;
+pub const MIN_WEIGHT_BPS: u16 = 100; // 1%, out of FEE_BPS_DENOMINATOR
+pub const MAX_WEIGHTED_IN_RATIO_BPS: u64 = 3_000; // a swap may add at most 30% to the input reserve
+pub const MAX_WEIGHTED_OUT_RATIO_BPS: u64 = 3_000; // and take at most 30% of the output reserve
+pub const TICK_ARRAY_SEED: &[u8] = b"tick_array";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const TICK_ARRAY_SIZE: usize = 32; // initializable ticks per tick array
+pub const MIN_TICK: i32 = -443_636; // sqrt price 2^-32
+pub const MAX_TICK: i32 = 443_636; // sqrt price 2^32
+pub const MAX_TICK_SPACING: u16 = 16_384;
+pub const MAX_FLASH_LOAN_FEE_BPS: u16 = 1_000; // 10%
+pub const MAX_SOL_FEE: u64 = 100_000_000; // 0.1 SOL
+pub const PARAM_CHANGE_SEED: &[u8] = b"param_change";
+pub const PARAM_CHANGE_DELAY: i64 = 172_800; // 2 days between queueing and executing
+pub const MINIMUM_LIQUIDITY: u64 = 1_000; // LP locked in the pool on its first deposit
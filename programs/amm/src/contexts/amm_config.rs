@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{AMM_CONFIG_SEED, MAX_FEE_TIERS, MAX_TRADE_FEE_BPS},
+    errors::*,
+    events::ConfigUpdated,
+    program::Amm,
+    state::AmmConfig,
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = AmmConfig::LEN,
+        seeds = [AMM_CONFIG_SEED],
+        bump,
+    )]
+    pub config: Box<Account<'info, AmmConfig>>,
+
+    /// The admin of the config, which has to be the program's upgrade authority
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Amm>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ AmmError::UnauthorizedAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: Only stored, receives protocol fees
+    pub fee_collector: AccountInfo<'info>,
+
+    // The account paying for all rents
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [AMM_CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ AmmError::UnauthorizedAdmin,
+    )]
+    pub config: Box<Account<'info, AmmConfig>>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(&mut self, fee_tiers: Vec<u16>, bump: u8) -> Result<()> {
+        let config = &mut self.config;
+        config.admin = self.admin.key();
+        config.fee_collector = self.fee_collector.key();
+        config.pool_count = 0;
+        config.bump = bump;
+        set_fee_tiers(config, &fee_tiers)?;
+
+        msg!("AMM config initialized");
+        msg!("Admin: {}", config.admin);
+        msg!("Fee collector: {}", config.fee_collector);
+        msg!("Fee tiers: {:?}", config.fee_tiers());
+        emit_config_updated(&self.config);
+        Ok(())
+    }
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_fee_tiers(&mut self, fee_tiers: Vec<u16>) -> Result<()> {
+        // Existing pools keep their fee; only new pools are held to the new tiers
+        set_fee_tiers(&mut self.config, &fee_tiers)?;
+        msg!("Fee tiers updated: {:?}", self.config.fee_tiers());
+        emit_config_updated(&self.config);
+        Ok(())
+    }
+
+    pub fn update_fee_collector(&mut self, fee_collector: Pubkey) -> Result<()> {
+        self.config.fee_collector = fee_collector;
+        msg!("Fee collector updated: {}", fee_collector);
+        emit_config_updated(&self.config);
+        Ok(())
+    }
+
+    pub fn update_config_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.config.admin = new_admin;
+        msg!("Config admin updated: {}", new_admin);
+        emit_config_updated(&self.config);
+        Ok(())
+    }
+}
+
+fn emit_config_updated(config: &Account<AmmConfig>) {
+    emit!(ConfigUpdated {
+        config: config.key(),
+        admin: config.admin,
+        fee_collector: config.fee_collector,
+        fee_tiers: config.fee_tiers().to_vec(),
+    });
+}
+
+fn set_fee_tiers(config: &mut AmmConfig, fee_tiers: &[u16]) -> Result<()> {
+    require!(
+        !fee_tiers.is_empty() && fee_tiers.len() <= MAX_FEE_TIERS,
+        AmmError::InvalidFeeTier
+    );
+    require!(
+        fee_tiers.iter().all(|&tier| tier <= MAX_TRADE_FEE_BPS),
+        AmmError::InvalidFee
+    );
+
+    config.fee_tiers = [0; MAX_FEE_TIERS];
+    config.fee_tiers[..fee_tiers.len()].copy_from_slice(fee_tiers);
+    config.fee_tier_count = fee_tiers.len() as u8;
+    Ok(())
+}
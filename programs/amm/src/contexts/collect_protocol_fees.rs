@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, TokenAccount, Mint};
+
+use crate::{
+    constants::{POOL_AUTHORITY_SEED, AMM_SEED},
+    contexts::transfers::transfer_checked_with_hook,
+    errors::AmmError,
+    events::ProtocolFeesCollected,
+    state::{Amm, Pool},
+};
+
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    #[account(
+        seeds = [
+            AMM_SEED,
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = admin @ AmmError::UnauthorizedAdmin,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+        constraint = !pool.locked @ AmmError::PoolLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority PDA
+    #[account(
+        seeds = [
+            pool.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            POOL_AUTHORITY_SEED,
+        ],
+        bump = pool.pool_authority_bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account receiving the accrued token A fees
+    #[account(mut,
+        token::mint = mint_a,
+        token::token_program = token_program_a,
+        constraint = treasury_account_a.owner == amm.roles.treasury @ AmmError::InvalidTreasury,
+    )]
+    pub treasury_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury token account receiving the accrued token B fees
+    #[account(mut,
+        token::mint = mint_b,
+        token::token_program = token_program_b,
+        constraint = treasury_account_b.owner == amm.roles.treasury @ AmmError::InvalidTreasury,
+    )]
+    pub treasury_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = admin.is_signer @ AmmError::NotSigner
+    )]
+    pub admin: Signer<'info>,
+
+    /// Solana ecosystem accounts
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    // Transfer hook accounts of whichever mints have a hook are passed as remaining accounts
+}
+
+impl<'info> CollectProtocolFees<'info> {
+    pub fn collect_protocol_fees(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let amount_a = self.pool.protocol_fees_a;
+        let amount_b = self.pool.protocol_fees_b;
+        msg!("Collecting protocol fees: A = {}, B = {}", amount_a, amount_b);
+
+        // Clear the counters before paying out
+        self.pool.protocol_fees_a = 0;
+        self.pool.protocol_fees_b = 0;
+
+        if amount_a > 0 {
+            self.transfer_from_pool_to_treasury(remaining_accounts, amount_a, true)?;
+        }
+        if amount_b > 0 {
+            self.transfer_from_pool_to_treasury(remaining_accounts, amount_b, false)?;
+        }
+
+        msg!("Protocol fees collected");
+        emit!(ProtocolFeesCollected {
+            amm: self.amm.key(),
+            pool: self.pool.key(),
+            amount_a,
+            amount_b,
+            treasury_account_a: self.treasury_account_a.key(),
+            treasury_account_b: self.treasury_account_b.key(),
+        });
+        Ok(())
+    }
+
+    fn transfer_from_pool_to_treasury(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        // Get pool authority bump from pool state
+        let pool_authority_bump = self.pool.pool_authority_bump;
+        
+        let pool_key = self.pool.key();
+        let mint_a_key = self.mint_a.key();
+        let mint_b_key = self.mint_b.key();
+        
+        // Create the signer seeds for the pool authority PDA
+        let authority_seeds = &[
+            pool_key.as_ref(),
+            mint_a_key.as_ref(),
+            mint_b_key.as_ref(),
+            POOL_AUTHORITY_SEED,
+            &[pool_authority_bump],
+        ];
+        
+        let (token_program, from, mint, to, decimals) = if is_token_a {
+            (&self.token_program_a, &self.pool_account_a, &self.mint_a, &self.treasury_account_a, self.mint_a.decimals)
+        } else {
+            (&self.token_program_b, &self.pool_account_b, &self.mint_b, &self.treasury_account_b, self.mint_b.decimals)
+        };
+        
+        // Transfer from pool to treasury with transfer hook, signed by the pool authority
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.pool_authority.to_account_info(),
+            remaining_accounts,
+            amount,
+            decimals,
+            &[&authority_seeds[..]],
+        )
+    }
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
-use crate::{constants::AMM_SEED, errors::*, state::Amm};
+use crate::{
+    constants::{AMM_CONFIG_SEED, AMM_SEED},
+    errors::*,
+    events::{AdminChanged, AdminProposed, AmmCreated, AmmMadeImmutable, RoleChanged},
+    state::{Amm, AmmConfig, AmmRoles, Role},
+};
 
 #[derive(Accounts)]
 #[instruction(mint_a: Pubkey, mint_b: Pubkey, sol_fee: u64)]
@@ -19,7 +24,15 @@ pub struct CreateAmm<'info> {
     )]
     pub amm: Box<Account<'info, Amm>>,
 
-    /// The admin of the AMM
+    /// Program-wide config; only its admin may create AMMs
+    #[account(
+        seeds = [AMM_CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ AmmError::UnauthorizedAdmin,
+    )]
+    pub config: Box<Account<'info, AmmConfig>>,
+
+    /// The admin of the AMM, which has to be the config admin
     #[account(
         constraint = admin.is_signer @ AmmError::UnauthorizedAdmin
     )]
@@ -33,7 +46,10 @@ pub struct CreateAmm<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    // Token mints for the AMM
+    // Token mints for the AMM, in canonical order so each pair has a single AMM
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ AmmError::MintsNotSorted
+    )]
     pub mint_a: Box<InterfaceAccount<'info, Mint>>,
     pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     
@@ -42,7 +58,6 @@ pub struct CreateAmm<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(new_admin: Pubkey)]
 pub struct UpdateAmm<'info> {
     #[account(
         mut,
@@ -52,6 +67,7 @@ pub struct UpdateAmm<'info> {
             mint_b.key().as_ref(),
         ],
         bump,
+        has_one = admin @ AmmError::UnauthorizedAdmin,
     )]
     pub amm: Account<'info, Amm>,
     
@@ -65,8 +81,7 @@ pub struct UpdateAmm<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(new_fee: u16)]
-pub struct UpdateFee<'info> {
+pub struct AcceptAdmin<'info> {
     #[account(
         mut,
         seeds = [
@@ -75,16 +90,15 @@ pub struct UpdateFee<'info> {
             mint_b.key().as_ref(),
         ],
         bump,
+        constraint = amm.pending_admin == pending_admin.key() @ AmmError::NotPendingAdmin,
     )]
     pub amm: Account<'info, Amm>,
     
     pub mint_a: Box<InterfaceAccount<'info, Mint>>,
     pub mint_b: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(
-        constraint = admin.is_signer @ AmmError::UnauthorizedAdmin
-    )]
-    pub admin: Signer<'info>,
+    /// The proposed admin, who has to sign to take over
+    pub pending_admin: Signer<'info>,
 }
 
 impl<'info> CreateAmm<'info> {
@@ -93,7 +107,9 @@ impl<'info> CreateAmm<'info> {
         mint_a: Pubkey, 
         mint_b: Pubkey,
         sol_fee: u64,
-        sol_fee_collector: Pubkey,
+        sol_fee_collector: Option<Pubkey>,
+        min_trade_fee_bps: u16,
+        max_trade_fee_bps: u16,
     ) -> Result<()> {
         msg!("Entering create_amm implementation");
         msg!("Mint A: {}", mint_a);
@@ -106,6 +122,17 @@ impl<'info> CreateAmm<'info> {
         }
         
         msg!("AMM not created yet, proceeding with creation");
+
+        // The AMM's trade fee bounds have to sit within the config's fee tiers
+        let (lowest_tier, highest_tier) = self.config.fee_tier_range();
+        require!(
+            min_trade_fee_bps >= lowest_tier && max_trade_fee_bps <= highest_tier,
+            AmmError::InvalidFee
+        );
+
+        // SOL fees go to the config's fee collector unless the admin names another one
+        let sol_fee_collector = sol_fee_collector.unwrap_or(self.config.fee_collector);
+        msg!("SOL fee collector: {}", sol_fee_collector);
         msg!("About to create Amm struct");
         
         // Create a deterministic pool ID from the mints
@@ -118,10 +145,17 @@ impl<'info> CreateAmm<'info> {
         let amm_struct = Amm {
             pool_id: pool_id_array,
             admin: self.admin.key(),
+            pending_admin: Pubkey::default(), // No admin transfer in progress
             sol_fee,
             sol_fee_collector,
             created: true,
             is_immutable: false, // Defaulting to false when created
+            min_trade_fee_bps,
+            max_trade_fee_bps,
+            protocol_fee_share_bps: 0, // Protocol fees are off until the admin sets a share
+            flash_loan_fee_bps: 0, // Flash loans are free until the admin sets a fee
+            roles: AmmRoles::all(self.admin.key()), // The admin holds every role until it grants them away
+            paused: false,
         };
         
         msg!("Amm struct created successfully");
@@ -132,6 +166,16 @@ impl<'info> CreateAmm<'info> {
         self.amm.set_inner(amm_struct);
         
         msg!("set_inner completed successfully");
+        emit!(AmmCreated {
+            amm: self.amm.key(),
+            mint_a,
+            mint_b,
+            admin: self.admin.key(),
+            sol_fee,
+            sol_fee_collector,
+            min_trade_fee_bps,
+            max_trade_fee_bps,
+        });
         msg!("AMM Created, setting created state to True");
         
         Ok(())
@@ -140,14 +184,16 @@ impl<'info> CreateAmm<'info> {
 }
 
 impl<'info> UpdateAmm<'info> {
-    pub fn update_admin(
+    /// First step of an admin transfer: records `new_admin`, who has to accept before
+    /// anything changes. Proposing the default key cancels a pending transfer.
+    pub fn propose_admin(
         &mut self,
         new_admin: Pubkey,
     ) -> Result<()> {
         
         // Check if the AMM has already been created
         if !self.amm.created {
-            msg!("AMM has not been created, cannot update admin");
+            msg!("AMM has not been created, cannot propose admin");
             return Err(AmmError::PoolDoesNotExist.into());
         }
         // Check if the AMM is immutable. If it is, then it cannot be updated.
@@ -161,53 +207,81 @@ impl<'info> UpdateAmm<'info> {
             return Err(AmmError::NotSigner.into());
         }
         
-        // Set the new admin
-        self.amm.admin = new_admin;
-        msg!("Admin Updated");
+        // Record the proposal, the admin stays in charge until it is accepted
+        self.amm.pending_admin = new_admin;
+        msg!("Admin Proposed: {}", new_admin);
+        emit!(AdminProposed {
+            amm: self.amm.key(),
+            admin: self.admin.key(),
+            pending_admin: new_admin,
+        });
         Ok(())
     }
 
-    // Function to make the AMM immutable
-    pub fn make_immutable(&mut self) -> Result<()> {
-        // Check if the current admin is the signer
-        if self.admin.key() != self.amm.admin {
-            msg!("Unauthorized Admin, Cannot make AMM immutable");
-            return Err(AmmError::UnauthorizedAdmin.into());
-        }
+    /// Hands `role` to `holder`. Roles stay manageable on an immutable AMM so that keys can
+    /// still be rotated and the AMM paused.
+    pub fn grant_role(
+        &mut self,
+        role: Role,
+        holder: Pubkey,
+    ) -> Result<()> {
+        *self.amm.roles.get_mut(role) = holder;
+        msg!("Role {:?} granted to {}", role, holder);
+        emit!(RoleChanged {
+            amm: self.amm.key(),
+            role,
+            holder,
+        });
+        Ok(())
+    }
+
+    /// Takes `role` back to the admin
+    pub fn revoke_role(&mut self, role: Role) -> Result<()> {
+        *self.amm.roles.get_mut(role) = self.amm.admin;
+        msg!("Role {:?} revoked", role);
+        emit!(RoleChanged {
+            amm: self.amm.key(),
+            role,
+            holder: self.amm.admin,
+        });
+        Ok(())
+    }
 
-        // Set the AMM as immutable
+    // Function to make the AMM immutable. Nothing clears the flag, so this is final.
+    pub fn make_immutable(&mut self) -> Result<()> {
+        // Set the AMM as immutable and drop any admin transfer still in flight
         self.amm.is_immutable = true;
+        self.amm.pending_admin = Pubkey::default();
         msg!("AMM is now immutable");
+        emit!(AmmMadeImmutable {
+            amm: self.amm.key(),
+            admin: self.admin.key(),
+        });
         Ok(())
     }
 }
 
-impl<'info> UpdateFee<'info> {
-    pub fn update_fee(
-        &mut self,
-        new_sol_fee: u64,
-    ) -> Result<()> {
-        
-        // Check if the AMM has already been created
-        if !self.amm.created {
-            msg!("AMM has not been created, cannot update fee");
-            return Err(AmmError::PoolDoesNotExist.into());
-        }
-        // Check if the AMM is immutable. If it is, then it cannot be updated.
+impl<'info> AcceptAdmin<'info> {
+    /// Second step of an admin transfer: the proposed admin takes over
+    pub fn accept_admin(&mut self) -> Result<()> {
+        // A transfer proposed before the AMM was made immutable cannot complete
         if self.amm.is_immutable {
             msg!("AMM Is now immutable. Cannot update.");
             return Err(AmmError::UnauthorizedAdmin.into());
         }
 
-        // Add in a check for the admin's signature
-        if !self.admin.is_signer {
-            return Err(AmmError::NotSigner.into());
-        }
-        
-        // Set the new SOL fee
-        self.amm.sol_fee = new_sol_fee;
-        msg!("SOL Fee Updated");
+        let old_admin = self.amm.admin;
+        let new_admin = self.pending_admin.key();
+        self.amm.admin = new_admin;
+        self.amm.pending_admin = Pubkey::default();
+        // Roles the old admin still held move with the admin seat
+        self.amm.roles.transfer_held(old_admin, new_admin);
+        msg!("Admin Updated: {}", self.amm.admin);
+        emit!(AdminChanged {
+            amm: self.amm.key(),
+            old_admin,
+            new_admin: self.amm.admin,
+        });
         Ok(())
     }
-
-} 
\ No newline at end of file
+}
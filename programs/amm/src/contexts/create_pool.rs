@@ -4,17 +4,74 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{
         Mint,
-        Token2022
+        Token2022,
+        TokenInterface,
     },
 };
 use crate::{
-    constants::{POOL_AUTHORITY_SEED, AMM_SEED},
+    constants::{POOL_AUTHORITY_SEED, AMM_SEED, AMM_CONFIG_SEED, MAX_AMP, MIN_AMP, OBSERVATIONS_SEED, FEE_BPS_DENOMINATOR, MIN_WEIGHT_BPS, MAX_TICK_SPACING, POOL_INDEX_SEED},
+    contexts::tick_math::tick_at_sqrt_price,
     errors::*,
-    state::{Amm, Pool},
+    events::PoolCreated,
+    state::{Amm, AmmConfig, CurveType, Observations, Pool, PoolIndex},
 };
 
 impl<'info> CreatePool<'info> {
-    pub fn create_pool(&mut self) -> Result<()> {
+    pub fn create_pool(
+        &mut self,
+        trade_fee_bps: u16,
+        curve_type: CurveType,
+        amp: u64,
+        weight_a: u16,
+        tick_spacing: u16,
+        sqrt_price_x64: u128,
+    ) -> Result<()> {
+        // The LP trading fee has to sit within the bounds set by the AMM admin
+        require!(
+            trade_fee_bps >= self.amm.min_trade_fee_bps && trade_fee_bps <= self.amm.max_trade_fee_bps,
+            AmmError::InvalidFee
+        );
+        // ...and be one of the program-wide fee tiers
+        require!(
+            self.config.fee_tiers().contains(&trade_fee_bps),
+            AmmError::InvalidFeeTier
+        );
+        // Only StableSwap pools use the amplification coefficient
+        let amp = match curve_type {
+            CurveType::StableSwap => {
+                require!((MIN_AMP..=MAX_AMP).contains(&amp), AmmError::InvalidAmplification);
+                amp
+            }
+            _ => 0,
+        };
+        // ...and only weighted pools use weights; B takes whatever A leaves
+        let (weight_a, weight_b) = match curve_type {
+            CurveType::Weighted => {
+                let weight_b = (FEE_BPS_DENOMINATOR as u16)
+                    .checked_sub(weight_a)
+                    .ok_or_else(|| error!(AmmError::InvalidWeights))?;
+                require!(
+                    weight_a >= MIN_WEIGHT_BPS && weight_b >= MIN_WEIGHT_BPS,
+                    AmmError::InvalidWeights
+                );
+                (weight_a, weight_b)
+            }
+            _ => (0, 0),
+        };
+        // ...and only concentrated pools have ticks and a stored price
+        let (tick_spacing, tick_current, sqrt_price_x64) = match curve_type {
+            CurveType::Concentrated => {
+                require!(
+                    tick_spacing > 0 && tick_spacing <= MAX_TICK_SPACING,
+                    AmmError::InvalidTickRange
+                );
+                let tick_current = tick_at_sqrt_price(sqrt_price_x64)
+                    .ok_or_else(|| error!(AmmError::InvalidSqrtPrice))?;
+                (tick_spacing, tick_current, sqrt_price_x64)
+            }
+            _ => (0, 0, 0),
+        };
+
         let pool = &mut self.pool;
         let pool_key = pool.key();
         pool.amm = self.amm.key();
@@ -23,6 +80,18 @@ impl<'info> CreatePool<'info> {
         pool.mint_b = self.mint_b.key();
         pool.lp_mint = self.mint_liquidity.key();
         pool.total_liquidity = 0;
+        pool.trade_fee_bps = trade_fee_bps;
+        pool.token_program_a = self.token_program_a.key();
+        pool.token_program_b = self.token_program_b.key();
+        pool.last_update_timestamp = Clock::get()?.unix_timestamp;
+        pool.curve_type = curve_type;
+        pool.amp_initial = amp;
+        pool.amp_target = amp;
+        pool.weight_a = weight_a;
+        pool.weight_b = weight_b;
+        pool.tick_spacing = tick_spacing;
+        pool.tick_current = tick_current;
+        pool.sqrt_price_x64 = sqrt_price_x64;
         
         // Store the pool authority bump for deterministic derivation
         let (_, bump) = Pubkey::find_program_address(
@@ -36,19 +105,57 @@ impl<'info> CreatePool<'info> {
         );
         pool.pool_authority_bump = bump;
 
+        // Start the TWAP history from the pool's creation
+        self.observations.pool = pool_key;
+        self.observations.record(pool);
+
+        // Append the pool to the on-chain registry
+        let index = self.config.pool_count;
+        self.pool_index.set_inner(PoolIndex {
+            index,
+            pool: pool_key,
+            amm: self.amm.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+        });
+        self.config.pool_count = index
+            .checked_add(1)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
         msg!("Pool created successfully");
         msg!("Pool authority bump: {}", bump);
+        msg!("Pool index: {}", index);
         msg!("LP mint: {}", self.mint_liquidity.key());
+        emit!(PoolCreated {
+            amm: self.amm.key(),
+            pool: pool_key,
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            lp_mint: self.mint_liquidity.key(),
+            index,
+            curve_type,
+            trade_fee_bps,
+            amp,
+            weight_a,
+            weight_b,
+            tick_spacing,
+            sqrt_price_x64,
+        });
 
         Ok(())
     }
 }
 
 #[derive(Accounts)]
+#[instruction(trade_fee_bps: u16, curve_type: CurveType, amp: u64, weight_a: u16, tick_spacing: u16, sqrt_price_x64: u128)]
 pub struct CreatePool<'info> {
     /// The account paying for all rents
     #[account(mut)]
     pub payer: Signer<'info>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         seeds = [
             AMM_SEED,
@@ -59,6 +166,26 @@ pub struct CreatePool<'info> {
     )]
     pub amm: Box<Account<'info, Amm>>,
 
+    #[account(
+        mut,
+        seeds = [AMM_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, AmmConfig>>,
+
+    /// Registry entry for this pool, at the next free index
+    #[account(
+        init,
+        payer = payer,
+        space = PoolIndex::LEN,
+        seeds = [
+            POOL_INDEX_SEED,
+            config.pool_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_index: Box<Account<'info, PoolIndex>>,
+
     #[account(
         init,
         payer = payer,
@@ -69,10 +196,20 @@ pub struct CreatePool<'info> {
             mint_b.key().as_ref(),
         ],
         bump,
-        constraint = mint_a.key() != mint_b.key() @ AmmError::InvalidPool,
+        constraint = mint_a.key() < mint_b.key() @ AmmError::MintsNotSorted,
     )]
     pub pool: Box<Account<'info, Pool>>,
     
+    /// Price history for TWAP reads
+    #[account(
+        init,
+        payer = payer,
+        space = Observations::LEN,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
     /// CHECK: Pool authority PDA - doesn't need to be created, just derived
     #[account(
         seeds = [
@@ -85,22 +222,31 @@ pub struct CreatePool<'info> {
     )]
     pub pool_authority: AccountInfo<'info>,
 
+    /// LP tokens take the finer of the two mints' decimals
     #[account(
         init,
         signer,
         payer = payer,
         mint::token_program = token_program,
-        mint::decimals = 6,
+        mint::decimals = mint_a.decimals.max(mint_b.decimals),
         mint::authority = pool_authority,
         mint::freeze_authority = pool_authority,
     )]
     pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
-    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
-    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
-
     /// Solana ecosystem accounts
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// LP mint program
     pub token_program: Program<'info, Token2022>,
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(
+        constraint = mint_a.to_account_info().owner == &token_program_a.key() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(
+        constraint = mint_b.to_account_info().owner == &token_program_b.key() @ AmmError::InvalidTokenProgram
+    )]
+    pub token_program_b: Interface<'info, TokenInterface>,
 } 
\ No newline at end of file
@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{Token2022, TokenAccount, Mint},
+    token_interface::{Token2022, TokenInterface, TokenAccount, Mint},
 };
 use crate::{
     constants::{POOL_AUTHORITY_SEED, AMM_SEED},
     state::{Amm, Pool},
     errors::AmmError,
+    events::PoolVaultsCreated,
     AmmError::InvalidMint,
 };
 
@@ -56,6 +57,9 @@ pub struct CreatePoolTokenAccounts<'info> {
         constraint = mint_b.key() == pool.mint_b @ InvalidMint
     )]
     pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    // The first deposit locks MINIMUM_LIQUIDITY LP in `pool_lp_account`, so that account
+    // has to be for the pool's own LP mint or no deposit could ever succeed
+    #[account(address = pool.lp_mint @ InvalidMint)]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
@@ -63,6 +67,7 @@ pub struct CreatePoolTokenAccounts<'info> {
         payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
     )]
     pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -71,6 +76,7 @@ pub struct CreatePoolTokenAccounts<'info> {
         payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
     )]
     pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -79,6 +85,7 @@ pub struct CreatePoolTokenAccounts<'info> {
         payer = payer,
         associated_token::mint = lp_mint,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program,
     )]
     pub pool_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -86,6 +93,12 @@ pub struct CreatePoolTokenAccounts<'info> {
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token2022>,
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
 }
 
 impl<'info> CreatePoolTokenAccounts<'info> {
@@ -123,6 +136,11 @@ impl<'info> CreatePoolTokenAccounts<'info> {
         msg!("Pool account B (vault_b): {}", self.pool_account_b.key());
         msg!("Pool LP account: {}", self.pool_lp_account.key());
         msg!("Pool authority: {}", self.pool_authority.key());
+        emit!(PoolVaultsCreated {
+            pool: self.pool.key(),
+            vault_a: self.pool.vault_a,
+            vault_b: self.pool.vault_b,
+        });
 
         Ok(())
     }
@@ -1,24 +1,30 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{Token2022, TokenAccount, Mint, mint_to, MintTo},
+    token_interface::{Token2022, TokenInterface, TokenAccount, Mint, mint_to, MintTo},
 };
-use anchor_spl::token_interface::spl_token_2022;
-use anchor_lang::solana_program::instruction::AccountMeta;
-use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::pubkey::Pubkey;
-use anchor_lang::solana_program::sysvar;
 use anchor_spl::associated_token::spl_associated_token_account;
-use std::str::FromStr;
 
 use crate::{
-    constants::{POOL_AUTHORITY_SEED, AMM_SEED},
+    constants::{POOL_AUTHORITY_SEED, AMM_SEED, MINIMUM_LIQUIDITY, OBSERVATIONS_SEED},
+    contexts::oracle::update_oracle,
+    contexts::transfers::transfer_checked_with_hook,
+    contexts::utils::{
+        calculate_deposit_amounts,
+        calculate_lp_tokens_to_mint,
+        calculate_stable_swap_initial_lp,
+        calculate_weighted_initial_lp,
+        get_transfer_fee,
+        get_transfer_inverse_fee,
+    },
     errors::AmmError,
-    state::{Amm, Pool},
+    events::LiquidityDeposited,
+    state::{Amm, CurveType, Observations, Pool},
 };
 
 #[derive(Accounts)]
-#[instruction(amount_a: u64, amount_b: u64)]
+#[instruction(max_amount_a: u64, max_amount_b: u64, min_lp_out: u64)]
 pub struct DepositLiquidity<'info> {
     #[account(
         seeds = [
@@ -31,15 +37,25 @@ pub struct DepositLiquidity<'info> {
     pub amm: Box<Account<'info, Amm>>,
 
     #[account(
+        mut,
         seeds = [
             amm.key().as_ref(),
             mint_a.key().as_ref(),
             mint_b.key().as_ref(),
         ],
         bump,
+        constraint = !pool.locked @ AmmError::PoolLocked,
+        constraint = !pool.is_paused(&amm) @ AmmError::Paused,
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
     /// CHECK: Pool authority PDA
     #[account(
         seeds = [
@@ -58,14 +74,14 @@ pub struct DepositLiquidity<'info> {
     #[account(mut,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_a,
     )]
     pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_b,
     )]
     pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -74,7 +90,7 @@ pub struct DepositLiquidity<'info> {
         payer = user,
         associated_token::mint = mint_a,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_a,
     )]
     pub user_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -83,7 +99,7 @@ pub struct DepositLiquidity<'info> {
         payer = user,
         associated_token::mint = mint_b,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_b,
     )]
     pub user_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
@@ -96,10 +112,14 @@ pub struct DepositLiquidity<'info> {
     )]
     pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: Pool LP account - will be validated manually
+    /// CHECK: Pool LP account - will be validated manually. Holds the LP locked on the first deposit
+    #[account(mut)]
     pub pool_lp_account: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidMint
+    )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
@@ -112,45 +132,29 @@ pub struct DepositLiquidity<'info> {
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Program<'info, Token2022>,
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
 
-    /// Transfer hook accounts for mint A
-    /// CHECK: Extra account meta list for mint A
-    pub extra_account_meta_list_a: AccountInfo<'info>,
-
-    /// CHECK: Mint trade counter for mint A
-    #[account(mut)]
-    pub mint_trade_counter_a: AccountInfo<'info>,
-
-    /// Transfer hook accounts for mint B
-    /// CHECK: Extra account meta list for mint B
-    pub extra_account_meta_list_b: AccountInfo<'info>,
-
-    /// CHECK: Mint trade counter for mint B
-    #[account(mut)]
-    pub mint_trade_counter_b: AccountInfo<'info>,
-
-    /// CHECK: Transfer hook program for mint A
-    pub transfer_hook_program_a: AccountInfo<'info>,
-
-    /// CHECK: Transfer hook program for mint B
-    pub transfer_hook_program_b: AccountInfo<'info>,
-
-    /// CHECK: Instructions sysvar
-    #[account(address = sysvar::instructions::ID)]
-    pub instructions_sysvar: AccountInfo<'info>,
+    // Transfer hook accounts of whichever mints have a hook are passed as remaining accounts
 }
 
 impl<'info> DepositLiquidity<'info> {
     pub fn deposit_liquidity(
         &mut self,
-        amount_a: u64,
-        amount_b: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+        max_amount_a: u64,
+        max_amount_b: u64,
+        min_lp_out: u64,
     ) -> Result<()> {
+        // Concentrated pools take liquidity through positions instead of LP tokens
+        require!(self.pool.curve_type != CurveType::Concentrated, AmmError::InvalidCurve);
         
-        
-        // Derive pool accounts on-chain using Token-2022 program
-        let token_2022_program_id = Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap();
-        
+        // Derive pool accounts on-chain; each vault belongs to its own mint's token program
+        // and the LP account to the Token-2022 LP mint
         // Manually derive associated token addresses to match TypeScript getAssociatedTokenAddressSync with allowOwnerOffCurve = true
         // This is equivalent to: getAssociatedTokenAddressSync(mint, owner, true, program_id)
         // The TypeScript version uses: findProgramAddressSync([owner.toBuffer(), programId.toBuffer(), mint.toBuffer()], ASSOCIATED_TOKEN_PROGRAM_ID)
@@ -159,7 +163,7 @@ impl<'info> DepositLiquidity<'info> {
         let (expected_pool_account_a, _) = Pubkey::find_program_address(
             &[
                 self.pool_authority.key().as_ref(),
-                token_2022_program_id.as_ref(),
+                self.token_program_a.key().as_ref(),
                 self.mint_a.key().as_ref(),
             ],
             &associated_token_program_id,
@@ -168,7 +172,7 @@ impl<'info> DepositLiquidity<'info> {
         let (expected_pool_account_b, _) = Pubkey::find_program_address(
             &[
                 self.pool_authority.key().as_ref(),
-                token_2022_program_id.as_ref(),
+                self.token_program_b.key().as_ref(),
                 self.mint_b.key().as_ref(),
             ],
             &associated_token_program_id,
@@ -177,7 +181,7 @@ impl<'info> DepositLiquidity<'info> {
         let (expected_pool_lp_account, _) = Pubkey::find_program_address(
             &[
                 self.pool_authority.key().as_ref(),
-                token_2022_program_id.as_ref(),
+                self.token_program.key().as_ref(),
                 self.lp_mint.key().as_ref(),
             ],
             &associated_token_program_id,
@@ -199,109 +203,175 @@ impl<'info> DepositLiquidity<'info> {
         
         msg!("Pool accounts validated successfully!");
         
+        // Accrue the TWAP accumulators on the reserves before they change
+        update_oracle(
+            &mut self.pool,
+            &mut self.observations,
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        )?;
+        
+        // Match the deposit to the current pool ratio and price it in LP tokens
+        let (amount_a, amount_b, lp_amount) = self.calculate_deposit(max_amount_a, max_amount_b)?;
+        msg!("Deposit amounts: A = {}, B = {}, LP = {}", amount_a, amount_b, lp_amount);
+        
+        // The first deposit leaves MINIMUM_LIQUIDITY with the pool for good, so the LP supply
+        // can never be driven back to a few units and each LP token inflated by a donation
+        let locked_lp = if self.lp_mint.supply == 0 { MINIMUM_LIQUIDITY } else { 0 };
+        let user_lp_amount = lp_amount
+            .checked_sub(locked_lp)
+            .ok_or_else(|| error!(AmmError::InsufficientLiquidity))?;
+        
+        // Check slippage
+        require!(user_lp_amount > 0, AmmError::InvalidAmount);
+        require!(user_lp_amount >= min_lp_out, AmmError::SlippageExceeded);
+        
         // Transfer tokens from user to pool
-        self.transfer_tokens_to_pool(amount_a, amount_b)?;
+        self.transfer_tokens_to_pool(remaining_accounts, amount_a, amount_b)?;
         
-        // Mint LP tokens to user
-        self.mint_lp_tokens(amount_a, amount_b)?;
+        // Mint LP tokens to user, and lock the minimum liquidity in the pool's own LP account
+        self.mint_lp_tokens(self.user_lp_account.to_account_info(), user_lp_amount)?;
+        if locked_lp > 0 {
+            msg!("Locking {} LP tokens", locked_lp);
+            self.mint_lp_tokens(self.pool_lp_account.to_account_info(), locked_lp)?;
+        }
+        
+        self.pool.total_liquidity = self.pool.total_liquidity
+            .checked_add(lp_amount)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        
+        emit!(LiquidityDeposited {
+            amm: self.amm.key(),
+            pool: self.pool.key(),
+            user: self.user.key(),
+            amount_a,
+            amount_b,
+            liquidity: user_lp_amount,
+            total_liquidity: self.pool.total_liquidity,
+        });
         
         Ok(())
     }
     
-    fn transfer_tokens_to_pool(&self, amount_a: u64, amount_b: u64) -> Result<()> {
-        // Transfer token A with transfer hook
+    fn calculate_deposit(&self, max_amount_a: u64, max_amount_b: u64) -> Result<(u64, u64, u64)> {
+        // Uncollected protocol fees belong to the treasury, not to the LPs
+        let (reserve_a, reserve_b) = self.pool.reserves(
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        );
+        let total_lp_supply = self.lp_mint.supply;
+        let mint_a = self.mint_a.to_account_info();
+        let mint_b = self.mint_b.to_account_info();
+        
+        // Price the deposit on what actually reaches the vaults after transfer fees
+        let net_max_amount_a = max_amount_a
+            .checked_sub(get_transfer_fee(&mint_a, max_amount_a)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let net_max_amount_b = max_amount_b
+            .checked_sub(get_transfer_fee(&mint_b, max_amount_b)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        
+        // The first deposit sets the price, so it takes both amounts as given.
+        // Every later deposit only takes the ratio-matched amounts.
+        let (net_amount_a, net_amount_b) = if total_lp_supply == 0 {
+            (net_max_amount_a, net_max_amount_b)
+        } else {
+            calculate_deposit_amounts(net_max_amount_a, net_max_amount_b, reserve_a, reserve_b)
+                .ok_or_else(|| error!(AmmError::InsufficientLiquidity))?
+        };
+        require!(net_amount_a > 0 && net_amount_b > 0, AmmError::InvalidAmount);
+        
+        // Later deposits are ratio-matched, so every curve mints pro rata. Only the
+        // first deposit depends on the curve.
+        let lp_amount = match self.pool.curve_type {
+            CurveType::StableSwap if total_lp_supply == 0 => calculate_stable_swap_initial_lp(
+                net_amount_a,
+                net_amount_b,
+                self.mint_a.decimals,
+                self.mint_b.decimals,
+                self.pool.amplification(Clock::get()?.unix_timestamp),
+            ),
+            CurveType::Weighted if total_lp_supply == 0 => calculate_weighted_initial_lp(
+                net_amount_a,
+                net_amount_b,
+                self.pool.weight_a,
+                self.pool.weight_b,
+            ),
+            _ => calculate_lp_tokens_to_mint(
+                net_amount_a,
+                net_amount_b,
+                total_lp_supply,
+                reserve_a,
+                reserve_b,
+            ),
+        }.ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        
+        // Gross the vault amounts back up to what the user has to send
+        let amount_a = if net_amount_a == net_max_amount_a {
+            max_amount_a
+        } else {
+            net_amount_a
+                .checked_add(get_transfer_inverse_fee(&mint_a, net_amount_a)?)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?
+        };
+        let amount_b = if net_amount_b == net_max_amount_b {
+            max_amount_b
+        } else {
+            net_amount_b
+                .checked_add(get_transfer_inverse_fee(&mint_b, net_amount_b)?)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?
+        };
+        require!(
+            amount_a <= max_amount_a && amount_b <= max_amount_b,
+            AmmError::SlippageExceeded
+        );
+        
+        Ok((amount_a, amount_b, lp_amount))
+    }
+    
+    fn transfer_tokens_to_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<()> {
         if amount_a > 0 {
-            
-            // Create mutable transfer_checked instruction
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.user_account_a.key(),
-                &self.mint_a.key(),
-                &self.pool_account_a.key(),
-                &self.user.key(),
-                &[&self.user.key()],
-                amount_a,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint A
-            // 1) validation account (extra account meta list)
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_a.key(), false));
-            // 2) the resolved extra account required by the hook (mint_trade_counter)
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_a.key(), false));
-            // 3) the transfer hook program id must be present so the token program can CPI into it
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_a.key(), false));
-            // 4) sysvar instructions (some hooks require it)
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-                        
-            // Invoke the modified instruction
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.user_account_a.to_account_info(),
-                self.mint_a.to_account_info(),
-                self.pool_account_a.to_account_info(),
-                self.user.to_account_info(),
-                self.extra_account_meta_list_a.to_account_info(),
-                self.mint_trade_counter_a.to_account_info(),
-                self.transfer_hook_program_a.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            // Account infos prepared for transfer
-            
-            invoke(&transfer_ix, account_infos)?;
+            self.transfer_from_user_to_pool(remaining_accounts, amount_a, true)?;
         }
-        
-        // Transfer token B with transfer hook
         if amount_b > 0 {
-            
-            // Create mutable transfer_checked instruction
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.user_account_b.key(),
-                &self.mint_b.key(),
-                &self.pool_account_b.key(),
-                &self.user.key(),
-                &[&self.user.key()],
-                amount_b,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint B
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-            
-            // Invoke the modified instruction
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.user_account_b.to_account_info(),
-                self.mint_b.to_account_info(),
-                self.pool_account_b.to_account_info(),
-                self.user.to_account_info(),
-                self.extra_account_meta_list_b.to_account_info(),
-                self.mint_trade_counter_b.to_account_info(),
-                self.transfer_hook_program_b.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            invoke(&transfer_ix, account_infos)?;
+            self.transfer_from_user_to_pool(remaining_accounts, amount_b, false)?;
         }
         
         Ok(())
     }
     
-    pub fn mint_lp_tokens(&self, amount_a: u64, amount_b: u64) -> Result<()> {
-        // Calculate LP tokens to mint using geometric mean (like the working AMM)
-        let lp_amount = if amount_a > 0 && amount_b > 0 {
-            // Geometric mean calculation similar to working AMM
-            ((amount_a as u128 * amount_b as u128) as f64).sqrt() as u64
+    fn transfer_from_user_to_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        let (token_program, from, mint, to, decimals) = if is_token_a {
+            (&self.token_program_a, &self.user_account_a, &self.mint_a, &self.pool_account_a, self.mint_a.decimals)
         } else {
-            (amount_a + amount_b) / 2 // Fallback for edge cases
+            (&self.token_program_b, &self.user_account_b, &self.mint_b, &self.pool_account_b, self.mint_b.decimals)
         };
         
-        
+        // Transfer from user to pool with transfer hook
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.user.to_account_info(),
+            remaining_accounts,
+            amount,
+            decimals,
+            &[],
+        )
+    }
+    
+    pub fn mint_lp_tokens(&self, to: AccountInfo<'info>, lp_amount: u64) -> Result<()> {
         // Use the stored bump from the pool state
         let pool_authority_bump = self.pool.pool_authority_bump;
         
@@ -321,7 +391,7 @@ impl<'info> DepositLiquidity<'info> {
         // Mint LP tokens using the same pattern as working AMM
         self.mint_token(
             self.lp_mint.to_account_info(),
-            self.user_lp_account.to_account_info(),
+            to,
             self.pool_authority.to_account_info(),
             lp_amount,
             signer_seeds,
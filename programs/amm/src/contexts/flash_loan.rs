@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar,
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    constants::POOL_AUTHORITY_SEED,
+    contexts::tick_math::Q64,
+    contexts::transfers::transfer_checked_with_hook,
+    contexts::utils::{calculate_protocol_fee, calculate_trade_fee, get_transfer_inverse_fee, mul_div},
+    errors::AmmError,
+    events::{FlashLoanBorrowed, FlashLoanRepaid},
+    state::{Amm, CurveType, Pool},
+};
+
+/// Position of `pool` in the `FlashLoan` accounts, used to match the repayment to the loan
+const FLASH_LOAN_POOL_ACCOUNT_INDEX: usize = 1;
+
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(mut, has_one = amm @ AmmError::InvalidPool)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Pool authority PDA
+    #[account(
+        seeds = [
+            pool.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            POOL_AUTHORITY_SEED,
+        ],
+        bump = pool.pool_authority_bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(address = pool.mint_a @ AmmError::InvalidMint)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pool.mint_b @ AmmError::InvalidMint)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.vault_a @ AmmError::InvalidPool)]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool.vault_b @ AmmError::InvalidPool)]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program_a,
+    )]
+    pub borrower_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program_b,
+    )]
+    pub borrower_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub borrower: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the repayment of a loan
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    // Transfer hook accounts of whichever mints have a hook are passed as remaining accounts
+}
+
+impl<'info> FlashLoan<'info> {
+    pub fn flash_borrow(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<()> {
+        msg!("Flash borrowing {} A and {} B", amount_a, amount_b);
+        require!(amount_a > 0 || amount_b > 0, AmmError::InvalidAmount);
+        require!(!self.pool.locked, AmmError::PoolLocked);
+        require!(!self.pool.is_paused(&self.amm), AmmError::Paused);
+        self.require_repayment()?;
+
+        // Uncollected protocol fees are not lent out
+        let (reserve_a, reserve_b) = self.pool.reserves(self.pool_account_a.amount, self.pool_account_b.amount);
+        require!(
+            amount_a <= reserve_a && amount_b <= reserve_b,
+            AmmError::InsufficientLiquidity
+        );
+
+        // Lock the pool until `flash_repay` brings the principal back
+        self.pool.locked = true;
+        self.pool.flash_loan_amount_a = amount_a;
+        self.pool.flash_loan_amount_b = amount_b;
+
+        self.transfer_from_pool(remaining_accounts, amount_a, true)?;
+        self.transfer_from_pool(remaining_accounts, amount_b, false)?;
+        emit!(FlashLoanBorrowed {
+            amm: self.amm.key(),
+            pool: self.pool.key(),
+            borrower: self.borrower.key(),
+            amount_a,
+            amount_b,
+        });
+        Ok(())
+    }
+
+    pub fn flash_repay(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require!(self.pool.locked, AmmError::NoFlashLoan);
+
+        let amount_a = self.pool.flash_loan_amount_a;
+        let amount_b = self.pool.flash_loan_amount_b;
+        let fee_a = self.repay(remaining_accounts, amount_a, true)?;
+        let fee_b = self.repay(remaining_accounts, amount_b, false)?;
+
+        self.pool.locked = false;
+        self.pool.flash_loan_amount_a = 0;
+        self.pool.flash_loan_amount_b = 0;
+        msg!("Flash loan repaid");
+        emit!(FlashLoanRepaid {
+            amm: self.amm.key(),
+            pool: self.pool.key(),
+            borrower: self.borrower.key(),
+            amount_a,
+            amount_b,
+            fee_a,
+            fee_b,
+        });
+        Ok(())
+    }
+
+    /// Fails unless a `flash_repay` on this pool follows in the same transaction
+    fn require_repayment(&self) -> Result<()> {
+        let instructions = self.instructions_sysvar.to_account_info();
+        let current_index = load_current_index_checked(&instructions)? as usize;
+        let pool_key = self.pool.key();
+
+        let mut index = current_index + 1;
+        while let Ok(instruction) = load_instruction_at_checked(index, &instructions) {
+            let is_repayment = instruction.program_id == crate::ID
+                && instruction.data.starts_with(crate::instruction::FlashRepay::DISCRIMINATOR)
+                && instruction
+                    .accounts
+                    .get(FLASH_LOAN_POOL_ACCOUNT_INDEX)
+                    .is_some_and(|account| account.pubkey == pool_key);
+            if is_repayment {
+                msg!("Repayment found at instruction {}", index);
+                return Ok(());
+            }
+            index += 1;
+        }
+
+        err!(AmmError::FlashLoanNotRepaid)
+    }
+
+    /// Takes back `amount` plus the flash loan fee so the vault receives both in full, and
+    /// shares the fee between the protocol and the LPs like a trade fee. Returns the fee.
+    fn repay(&mut self, remaining_accounts: &[AccountInfo<'info>], amount: u64, is_token_a: bool) -> Result<u64> {
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        // Same rounding as the trade fee, up in favour of the pool
+        let fee = calculate_trade_fee(amount, self.amm.flash_loan_fee_bps)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let owed = amount
+            .checked_add(fee)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let mint = if is_token_a {
+            self.mint_a.to_account_info()
+        } else {
+            self.mint_b.to_account_info()
+        };
+        let repay_amount = owed
+            .checked_add(get_transfer_inverse_fee(&mint, owed)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        msg!("Repaying {} ({} principal, {} fee)", repay_amount, amount, fee);
+        self.transfer_to_pool(remaining_accounts, repay_amount, is_token_a)?;
+
+        let mut protocol_fee = calculate_protocol_fee(fee, self.amm.protocol_fee_share_bps)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let pool = &mut self.pool;
+        if pool.curve_type == CurveType::Concentrated {
+            // Concentrated LPs earn through fee growth; with no active liquidity the protocol keeps it all
+            if pool.liquidity > 0 {
+                let fee_growth = mul_div((fee - protocol_fee) as u128, Q64, pool.liquidity)
+                    .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+                let fee_growth_global = if is_token_a {
+                    &mut pool.fee_growth_global_a_x64
+                } else {
+                    &mut pool.fee_growth_global_b_x64
+                };
+                *fee_growth_global = fee_growth_global.wrapping_add(fee_growth);
+            } else {
+                protocol_fee = fee;
+            }
+        }
+        // On every other curve the LP part simply stays in the reserves
+        let accrued = if is_token_a {
+            &mut pool.protocol_fees_a
+        } else {
+            &mut pool.protocol_fees_b
+        };
+        *accrued = accrued
+            .checked_add(protocol_fee)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        Ok(fee)
+    }
+
+    fn transfer_to_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        let (token_program, from, mint, to) = if is_token_a {
+            (&self.token_program_a, &self.borrower_account_a, &self.mint_a, &self.pool_account_a)
+        } else {
+            (&self.token_program_b, &self.borrower_account_b, &self.mint_b, &self.pool_account_b)
+        };
+
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.borrower.to_account_info(),
+            remaining_accounts,
+            amount,
+            mint.decimals,
+            &[],
+        )
+    }
+
+    fn transfer_from_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let pool_key = self.pool.key();
+        let mint_a_key = self.mint_a.key();
+        let mint_b_key = self.mint_b.key();
+        let authority_seeds = &[
+            pool_key.as_ref(),
+            mint_a_key.as_ref(),
+            mint_b_key.as_ref(),
+            POOL_AUTHORITY_SEED,
+            &[self.pool.pool_authority_bump],
+        ];
+
+        let (token_program, from, mint, to) = if is_token_a {
+            (&self.token_program_a, &self.pool_account_a, &self.mint_a, &self.borrower_account_a)
+        } else {
+            (&self.token_program_b, &self.pool_account_b, &self.mint_b, &self.borrower_account_b)
+        };
+
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.pool_authority.to_account_info(),
+            remaining_accounts,
+            amount,
+            mint.decimals,
+            &[&authority_seeds[..]],
+        )
+    }
+}
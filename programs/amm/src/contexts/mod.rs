@@ -1,18 +1,46 @@
 use anchor_lang::prelude::*;
 
+pub mod amm_config;
+pub mod collect_protocol_fees;
 pub mod create_amm;
 pub mod create_pool;
 pub mod create_token_accounts;
 pub mod deposit_liquidity;
+pub mod flash_loan;
+pub mod oracle;
+pub mod param_change;
+pub mod pause;
+pub mod position;
+pub mod position_liquidity;
+pub mod ramp_amplification;
+pub mod swap_concentrated;
 pub mod swap_exact_tokens_for_tokens;
+pub mod swap_route;
+pub mod swap_tokens_for_exact_tokens;
+pub mod tick_array;
+pub mod tick_math;
+pub mod transfers;
 pub mod utils;
+pub mod withdraw_liquidity;
 
+pub use amm_config::*;
+pub use collect_protocol_fees::*;
 pub use create_amm::*;
 pub use create_pool::*;
 pub use create_token_accounts::*;
 pub use deposit_liquidity::*;
+pub use flash_loan::*;
+pub use oracle::*;
+pub use param_change::*;
+pub use pause::*;
+pub use position::*;
+pub use position_liquidity::*;
+pub use ramp_amplification::*;
 pub use swap_exact_tokens_for_tokens::*;
+pub use swap_route::*;
+pub use tick_array::*;
 pub use utils::*;
+pub use withdraw_liquidity::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct DepositLiquidityBumps {
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::{
+    constants::OBSERVATIONS_SEED,
+    errors::AmmError,
+    state::{Observations, Pool},
+};
+
+/// Brings the pool's price accumulators up to now and snapshots them. Called at the
+/// start of every swap and liquidity change, before any vault balance moves.
+pub(crate) fn update_oracle(
+    pool: &mut Pool,
+    observations: &mut Observations,
+    vault_a_amount: u64,
+    vault_b_amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let (reserve_a, reserve_b) = pool.reserves(vault_a_amount, vault_b_amount);
+    pool.update_price_accumulators(reserve_a, reserve_b, now);
+    observations.record(pool);
+    Ok(())
+}
+
+/// Time-weighted average prices returned by `get_twap`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Twap {
+    /// Average price of A in B over the window, Q64.64
+    pub price_a: u128,
+    /// Average price of B in A over the window, Q64.64
+    pub price_b: u128,
+    /// Seconds actually covered, at least the requested window
+    pub window: i64,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    #[account(address = pool.vault_a @ AmmError::InvalidPool)]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.vault_b @ AmmError::InvalidPool)]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> GetTwap<'info> {
+    pub fn get_twap(&self, window: i64) -> Result<Twap> {
+        require!(window > 0, AmmError::InvalidAmount);
+
+        // Extend the accumulators to now on a copy; this is a read-only view
+        let now = Clock::get()?.unix_timestamp;
+        let mut pool = (**self.pool).clone();
+        let (reserve_a, reserve_b) = pool.reserves(self.pool_account_a.amount, self.pool_account_b.amount);
+        pool.update_price_accumulators(reserve_a, reserve_b, now);
+
+        let start = self
+            .observations
+            .at_or_before(now.saturating_sub(window))
+            .ok_or_else(|| error!(AmmError::InsufficientPriceHistory))?;
+        let elapsed = now - start.timestamp;
+
+        // Wrapping differences undo any accumulator overflow in between
+        let price_a = pool.price_a_cumulative.wrapping_sub(start.price_a_cumulative) / elapsed as u128;
+        let price_b = pool.price_b_cumulative.wrapping_sub(start.price_b_cumulative) / elapsed as u128;
+        msg!("TWAP over {}s: A = {}, B = {} (Q64.64)", elapsed, price_a, price_b);
+
+        Ok(Twap {
+            price_a,
+            price_b,
+            window: elapsed,
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{
+        FEE_BPS_DENOMINATOR,
+        MAX_FLASH_LOAN_FEE_BPS,
+        MAX_SOL_FEE,
+        MAX_TRADE_FEE_BPS,
+        PARAM_CHANGE_DELAY,
+        PARAM_CHANGE_SEED,
+    },
+    errors::AmmError,
+    events::{FeeUpdated, ParamChangeCancelled, ParamChangeExecuted, ParamChangeQueued},
+    state::{Amm, ParamChange, PendingParamChange},
+};
+
+#[derive(Accounts)]
+#[instruction(change: ParamChange)]
+pub struct QueueParamChange<'info> {
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingParamChange::LEN,
+        seeds = [
+            PARAM_CHANGE_SEED,
+            amm.key().as_ref(),
+            &[change.kind()],
+        ],
+        bump,
+    )]
+    pub pending_change: Box<Account<'info, PendingParamChange>>,
+
+    /// Fee manager or admin, depending on the parameter, who also pays the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteParamChange<'info> {
+    #[account(mut)]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PARAM_CHANGE_SEED,
+            amm.key().as_ref(),
+            &[pending_change.change.kind()],
+        ],
+        bump = pending_change.bump,
+        has_one = amm @ AmmError::InvalidParamChange,
+        has_one = proposer @ AmmError::InvalidParamChange,
+        close = proposer,
+    )]
+    pub pending_change: Box<Account<'info, PendingParamChange>>,
+
+    /// CHECK: Account that queued the change, receiving the rent back
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParamChange<'info> {
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            PARAM_CHANGE_SEED,
+            amm.key().as_ref(),
+            &[pending_change.change.kind()],
+        ],
+        bump = pending_change.bump,
+        has_one = amm @ AmmError::InvalidParamChange,
+        has_one = proposer @ AmmError::InvalidParamChange,
+        close = proposer,
+    )]
+    pub pending_change: Box<Account<'info, PendingParamChange>>,
+
+    /// CHECK: Account that queued the change, receiving the rent back
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// Holder of the role that may queue the change, or the admin
+    pub authority: Signer<'info>,
+}
+
+impl<'info> QueueParamChange<'info> {
+    pub fn queue_param_change(&mut self, change: ParamChange, bump: u8) -> Result<()> {
+        msg!("Queueing parameter change: {:?}", change);
+
+        // Check if the AMM has already been created
+        if !self.amm.created {
+            msg!("AMM has not been created, cannot queue a change");
+            return Err(AmmError::PoolDoesNotExist.into());
+        }
+        // Check if the AMM is immutable. If it is, then it cannot be updated.
+        if self.amm.is_immutable {
+            msg!("AMM Is now immutable. Cannot update.");
+            return Err(AmmError::UnauthorizedAdmin.into());
+        }
+
+        require_keys_eq!(
+            self.authority.key(),
+            change.authority(&self.amm),
+            change.unauthorized_error()
+        );
+        validate_param_change(&change)?;
+
+        let eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(PARAM_CHANGE_DELAY)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        self.pending_change.set_inner(PendingParamChange {
+            amm: self.amm.key(),
+            change,
+            eta,
+            proposer: self.authority.key(),
+            bump,
+        });
+
+        msg!("Parameter change executable from {}", eta);
+        emit!(ParamChangeQueued {
+            amm: self.amm.key(),
+            pending_change: self.pending_change.key(),
+            change,
+            eta,
+            proposer: self.authority.key(),
+        });
+        Ok(())
+    }
+}
+
+impl<'info> ExecuteParamChange<'info> {
+    /// Applies a queued change once its delay has passed. Anyone can execute it, the
+    /// decision was taken when it was queued, as long as the proposer still holds its role.
+    pub fn execute_param_change(&mut self) -> Result<()> {
+        let change = self.pending_change.change;
+        msg!("Executing parameter change: {:?}", change);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.pending_change.eta, AmmError::TimelockNotElapsed);
+
+        // A change queued before the AMM was made immutable cannot go through
+        if self.amm.is_immutable {
+            msg!("AMM Is now immutable. Cannot update.");
+            return Err(AmmError::UnauthorizedAdmin.into());
+        }
+        // The change only stands while whoever queued it still holds the role that allowed it
+        require_keys_eq!(
+            self.pending_change.proposer,
+            change.authority(&self.amm),
+            change.unauthorized_error()
+        );
+        validate_param_change(&change)?;
+
+        let amm = &mut self.amm;
+        match change {
+            ParamChange::SolFee(sol_fee) => amm.sol_fee = sol_fee,
+            ParamChange::SolFeeCollector(collector) => amm.sol_fee_collector = collector,
+            ParamChange::TradeFeeBounds { min_bps, max_bps } => {
+                // Only affects pools created from now on
+                amm.min_trade_fee_bps = min_bps;
+                amm.max_trade_fee_bps = max_bps;
+            }
+            ParamChange::ProtocolFeeShare(share_bps) => amm.protocol_fee_share_bps = share_bps,
+            ParamChange::FlashLoanFee(fee_bps) => amm.flash_loan_fee_bps = fee_bps,
+        }
+
+        msg!("Parameter change executed");
+        emit!(ParamChangeExecuted {
+            amm: self.amm.key(),
+            pending_change: self.pending_change.key(),
+            change,
+        });
+        emit!(FeeUpdated {
+            amm: self.amm.key(),
+            sol_fee: self.amm.sol_fee,
+            sol_fee_collector: self.amm.sol_fee_collector,
+            min_trade_fee_bps: self.amm.min_trade_fee_bps,
+            max_trade_fee_bps: self.amm.max_trade_fee_bps,
+            protocol_fee_share_bps: self.amm.protocol_fee_share_bps,
+            flash_loan_fee_bps: self.amm.flash_loan_fee_bps,
+        });
+        Ok(())
+    }
+}
+
+impl<'info> CancelParamChange<'info> {
+    pub fn cancel_param_change(&mut self) -> Result<()> {
+        let change = self.pending_change.change;
+        msg!("Cancelling parameter change: {:?}", change);
+
+        // The admin can call off any change, other roles only their own kind
+        let authority = self.authority.key();
+        require!(
+            authority == change.authority(&self.amm) || authority == self.amm.admin,
+            change.unauthorized_error()
+        );
+
+        emit!(ParamChangeCancelled {
+            amm: self.amm.key(),
+            pending_change: self.pending_change.key(),
+            change,
+            cancelled_by: authority,
+        });
+        Ok(())
+    }
+}
+
+impl ParamChange {
+    /// Error for a signer that is not `authority`
+    fn unauthorized_error(&self) -> AmmError {
+        match self {
+            ParamChange::SolFee(_) | ParamChange::ProtocolFeeShare(_) | ParamChange::FlashLoanFee(_) => {
+                AmmError::UnauthorizedFeeManager
+            }
+            ParamChange::SolFeeCollector(_) | ParamChange::TradeFeeBounds { .. } => AmmError::UnauthorizedAdmin,
+        }
+    }
+}
+
+/// Same bounds as at AMM creation, checked when a change is queued and again when it executes
+fn validate_param_change(change: &ParamChange) -> Result<()> {
+    match *change {
+        ParamChange::SolFee(sol_fee) => {
+            require!(sol_fee > 0 && sol_fee <= MAX_SOL_FEE, AmmError::InvalidFee);
+        }
+        ParamChange::SolFeeCollector(_) => {}
+        ParamChange::TradeFeeBounds { min_bps, max_bps } => {
+            require!(min_bps <= max_bps && max_bps <= MAX_TRADE_FEE_BPS, AmmError::InvalidFee);
+        }
+        ParamChange::ProtocolFeeShare(share_bps) => {
+            // The protocol can take at most the whole trade fee
+            require!(share_bps as u64 <= FEE_BPS_DENOMINATOR, AmmError::InvalidFee);
+        }
+        ParamChange::FlashLoanFee(fee_bps) => {
+            require!(fee_bps <= MAX_FLASH_LOAN_FEE_BPS, AmmError::InvalidFee);
+        }
+    }
+    Ok(())
+}
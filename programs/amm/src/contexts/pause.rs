@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{
+    errors::AmmError,
+    events::PauseUpdated,
+    state::{Amm, Pool},
+};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        constraint = amm.roles.pauser == pauser.key() @ AmmError::UnauthorizedPauser,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    /// Pool to pause or unpause; the whole AMM when omitted
+    #[account(
+        mut,
+        has_one = amm @ AmmError::InvalidPool,
+    )]
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    pub pauser: Signer<'info>,
+}
+
+impl<'info> SetPaused<'info> {
+    /// Stops or resumes swaps, deposits and flash loans. Withdrawals are never paused so
+    /// LPs can always exit.
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        match &mut self.pool {
+            Some(pool) => {
+                pool.paused = paused;
+                msg!("Pool {} {}", pool.key(), if paused { "paused" } else { "unpaused" });
+            }
+            None => {
+                self.amm.paused = paused;
+                msg!("AMM {} {}", self.amm.key(), if paused { "paused" } else { "unpaused" });
+            }
+        }
+        emit!(PauseUpdated {
+            amm: self.amm.key(),
+            pool: self.pool.as_ref().map(|pool| pool.key()),
+            paused,
+            pauser: self.pauser.key(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_TICK, MIN_TICK, POSITION_SEED},
+    errors::AmmError,
+    events::{PositionClosed, PositionOpened},
+    state::{CurveType, Pool, Position},
+};
+
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct OpenPosition<'info> {
+    /// Owner of the new position, who also pays its rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(constraint = pool.curve_type == CurveType::Concentrated @ AmmError::InvalidCurve)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::LEN,
+        seeds = [
+            POSITION_SEED,
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenPosition<'info> {
+    pub fn open_position(&mut self, tick_lower: i32, tick_upper: i32, bump: u8) -> Result<()> {
+        // Both ends have to be initializable ticks of the pool
+        let spacing = self.pool.tick_spacing as i32;
+        require!(
+            tick_lower < tick_upper
+                && tick_lower >= MIN_TICK
+                && tick_upper <= MAX_TICK
+                && tick_lower % spacing == 0
+                && tick_upper % spacing == 0,
+            AmmError::InvalidTickRange
+        );
+
+        self.position.set_inner(Position {
+            pool: self.pool.key(),
+            owner: self.owner.key(),
+            tick_lower,
+            tick_upper,
+            bump,
+            ..Default::default()
+        });
+
+        msg!("Position opened over ticks [{}, {})", tick_lower, tick_upper);
+        emit!(PositionOpened {
+            pool: self.pool.key(),
+            position: self.position.key(),
+            owner: self.owner.key(),
+            tick_lower,
+            tick_upper,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    /// Owner of the position, who gets its rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ AmmError::InvalidPositionOwner,
+        close = owner,
+    )]
+    pub position: Box<Account<'info, Position>>,
+}
+
+impl<'info> ClosePosition<'info> {
+    pub fn close_position(&mut self) -> Result<()> {
+        // Liquidity and fees have to be taken out first, or they would be lost with the account
+        require!(
+            self.position.liquidity == 0 && self.position.fees_owed_a == 0 && self.position.fees_owed_b == 0,
+            AmmError::PositionNotEmpty
+        );
+
+        msg!("Position {} closed", self.position.key());
+        emit!(PositionClosed {
+            pool: self.position.pool,
+            position: self.position.key(),
+            owner: self.owner.key(),
+        });
+        Ok(())
+    }
+}
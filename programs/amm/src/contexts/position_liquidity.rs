@@ -0,0 +1,397 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{
+    constants::POOL_AUTHORITY_SEED,
+    contexts::tick_array::TickArrays,
+    contexts::tick_math::{add_liquidity_delta, amount_a_delta, amount_b_delta, sqrt_price_at_tick, MAX_LIQUIDITY, Q64},
+    contexts::transfers::transfer_checked_with_hook,
+    contexts::utils::{get_transfer_fee, get_transfer_inverse_fee, mul_div},
+    errors::AmmError,
+    events::{PositionFeesCollected, PositionLiquidityChanged},
+    state::{Amm, CurveType, Pool, Position, Tick},
+};
+
+#[derive(Accounts)]
+pub struct ModifyPosition<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(address = pool.amm @ AmmError::InvalidPool)]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        constraint = pool.curve_type == CurveType::Concentrated @ AmmError::InvalidCurve,
+        constraint = !pool.locked @ AmmError::PoolLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        has_one = owner @ AmmError::InvalidPositionOwner,
+        has_one = pool @ AmmError::InvalidPool,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: Pool authority PDA
+    #[account(
+        seeds = [
+            pool.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            POOL_AUTHORITY_SEED,
+        ],
+        bump = pool.pool_authority_bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(address = pool.mint_a @ AmmError::InvalidMint)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pool.mint_b @ AmmError::InvalidMint)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.vault_a @ AmmError::InvalidPool)]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool.vault_b @ AmmError::InvalidPool)]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program_a,
+    )]
+    pub owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program_b,
+    )]
+    pub owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    // The tick arrays holding the position's ticks and the transfer hook accounts of
+    // whichever mints have a hook are passed as remaining accounts
+}
+
+impl<'info> ModifyPosition<'info> {
+    pub fn increase_liquidity(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        liquidity: u128,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        msg!("Adding {} liquidity to position {}", liquidity, self.position.key());
+        // Only new liquidity is paused; positions can always be drawn down
+        require!(!self.pool.is_paused(&self.amm), AmmError::Paused);
+        require!(liquidity > 0 && liquidity <= MAX_LIQUIDITY, AmmError::InvalidAmount);
+        let delta = liquidity as i128;
+        // Fails if the position, either tick or the pool would go past MAX_LIQUIDITY
+        self.update_position(remaining_accounts, delta)?;
+
+        // Rounded up, and grossed up so the vaults receive the full amounts after any transfer fee
+        let (pool_amount_a, pool_amount_b) = self.liquidity_amounts(liquidity, true)?;
+        let mint_a = self.mint_a.to_account_info();
+        let mint_b = self.mint_b.to_account_info();
+        let amount_a = pool_amount_a
+            .checked_add(get_transfer_inverse_fee(&mint_a, pool_amount_a)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let amount_b = pool_amount_b
+            .checked_add(get_transfer_inverse_fee(&mint_b, pool_amount_b)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        msg!("Depositing {} A and {} B", amount_a, amount_b);
+        require!(
+            amount_a <= max_amount_a && amount_b <= max_amount_b,
+            AmmError::SlippageExceeded
+        );
+
+        self.transfer_to_pool(remaining_accounts, amount_a, true)?;
+        self.transfer_to_pool(remaining_accounts, amount_b, false)?;
+        self.emit_liquidity_changed(delta, pool_amount_a, pool_amount_b);
+        Ok(())
+    }
+
+    pub fn decrease_liquidity(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        liquidity: u128,
+        min_amount_a: u64,
+        min_amount_b: u64,
+    ) -> Result<()> {
+        msg!("Removing {} liquidity from position {}", liquidity, self.position.key());
+        require!(
+            liquidity > 0 && liquidity <= self.position.liquidity,
+            AmmError::InvalidAmount
+        );
+        let delta = i128::try_from(liquidity).map_err(|_| error!(AmmError::InvalidAmount))?;
+        self.update_position(remaining_accounts, -delta)?;
+
+        // Rounded down; slippage is checked on what arrives after any transfer fee
+        let (pool_amount_a, pool_amount_b) = self.liquidity_amounts(liquidity, false)?;
+        let mint_a = self.mint_a.to_account_info();
+        let mint_b = self.mint_b.to_account_info();
+        let amount_a = pool_amount_a - get_transfer_fee(&mint_a, pool_amount_a)?;
+        let amount_b = pool_amount_b - get_transfer_fee(&mint_b, pool_amount_b)?;
+        msg!("Withdrawing {} A and {} B", amount_a, amount_b);
+        require!(
+            amount_a >= min_amount_a && amount_b >= min_amount_b,
+            AmmError::SlippageExceeded
+        );
+
+        self.transfer_from_pool(remaining_accounts, pool_amount_a, true)?;
+        self.transfer_from_pool(remaining_accounts, pool_amount_b, false)?;
+        self.emit_liquidity_changed(-delta, pool_amount_a, pool_amount_b);
+        Ok(())
+    }
+
+    pub fn collect_fees(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // Bring the fees owed up to now before paying them out
+        self.update_position(remaining_accounts, 0)?;
+
+        let fees_a = self.position.fees_owed_a;
+        let fees_b = self.position.fees_owed_b;
+        self.position.fees_owed_a = 0;
+        self.position.fees_owed_b = 0;
+        msg!("Collecting {} A and {} B in fees", fees_a, fees_b);
+
+        self.transfer_from_pool(remaining_accounts, fees_a, true)?;
+        self.transfer_from_pool(remaining_accounts, fees_b, false)?;
+        emit!(PositionFeesCollected {
+            pool: self.pool.key(),
+            position: self.position.key(),
+            owner: self.owner.key(),
+            amount_a: fees_a,
+            amount_b: fees_b,
+        });
+        Ok(())
+    }
+
+    /// Reports a liquidity change with the amounts that entered or left the vaults
+    fn emit_liquidity_changed(&self, liquidity_delta: i128, amount_a: u64, amount_b: u64) {
+        emit!(PositionLiquidityChanged {
+            pool: self.pool.key(),
+            position: self.position.key(),
+            owner: self.owner.key(),
+            liquidity_delta,
+            amount_a,
+            amount_b,
+            liquidity: self.position.liquidity,
+        });
+    }
+
+    /// Applies a liquidity change to the position, its two ticks and, when the range
+    /// holds the current price, the pool, and credits the fees earned since the last update
+    fn update_position(&mut self, remaining_accounts: &[AccountInfo<'info>], delta: i128) -> Result<()> {
+        let mut tick_arrays = TickArrays::new(self.pool.key(), self.pool.tick_spacing, remaining_accounts);
+        let pool = &mut self.pool;
+        let position = &mut self.position;
+        let (tick_lower, tick_upper) = (position.tick_lower, position.tick_upper);
+
+        if delta != 0 {
+            update_tick(&mut tick_arrays, pool, tick_lower, delta, false)?;
+            update_tick(&mut tick_arrays, pool, tick_upper, delta, true)?;
+        }
+
+        // An empty position that is not being topped up has no ticks to read
+        if position.liquidity > 0 || delta != 0 {
+            let (inside_a, inside_b) = fee_growth_inside(&mut tick_arrays, pool, tick_lower, tick_upper)?;
+
+            // Fees earned on the liquidity held since the last update
+            let earned_a = mul_div(
+                position.liquidity,
+                inside_a.wrapping_sub(position.fee_growth_inside_a_last_x64),
+                Q64,
+            ).and_then(|earned| u64::try_from(earned).ok());
+            let earned_b = mul_div(
+                position.liquidity,
+                inside_b.wrapping_sub(position.fee_growth_inside_b_last_x64),
+                Q64,
+            ).and_then(|earned| u64::try_from(earned).ok());
+            position.fees_owed_a = earned_a
+                .and_then(|earned| position.fees_owed_a.checked_add(earned))
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            position.fees_owed_b = earned_b
+                .and_then(|earned| position.fees_owed_b.checked_add(earned))
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            position.fee_growth_inside_a_last_x64 = inside_a;
+            position.fee_growth_inside_b_last_x64 = inside_b;
+        }
+
+        if delta != 0 {
+            position.liquidity = add_liquidity_delta(position.liquidity, delta)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            if (tick_lower..tick_upper).contains(&pool.tick_current) {
+                pool.liquidity = add_liquidity_delta(pool.liquidity, delta)
+                    .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            }
+        }
+
+        // Ticks no position references any more go back to uninitialized
+        if delta < 0 {
+            for tick_index in [tick_lower, tick_upper] {
+                let tick = tick_arrays.tick_mut(tick_index)?;
+                if tick.liquidity_gross == 0 {
+                    *tick = Tick::default();
+                }
+            }
+        }
+
+        tick_arrays.exit()
+    }
+
+    /// Token amounts backing `liquidity` over the position's range at the current price
+    fn liquidity_amounts(&self, liquidity: u128, round_up: bool) -> Result<(u64, u64)> {
+        let sqrt_price_lower = sqrt_price_at_tick(self.position.tick_lower)
+            .ok_or_else(|| error!(AmmError::InvalidTickRange))?;
+        let sqrt_price_upper = sqrt_price_at_tick(self.position.tick_upper)
+            .ok_or_else(|| error!(AmmError::InvalidTickRange))?;
+        let sqrt_price = self.pool.sqrt_price_x64;
+
+        // Below the range the position is all A, above it all B, and in between a mix
+        let (amount_a, amount_b) = if self.pool.tick_current < self.position.tick_lower {
+            (amount_a_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up), Some(0))
+        } else if self.pool.tick_current < self.position.tick_upper {
+            (
+                amount_a_delta(sqrt_price, sqrt_price_upper, liquidity, round_up),
+                amount_b_delta(sqrt_price_lower, sqrt_price, liquidity, round_up),
+            )
+        } else {
+            (Some(0), amount_b_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up))
+        };
+
+        let amount_a = amount_a.and_then(|amount| u64::try_from(amount).ok());
+        let amount_b = amount_b.and_then(|amount| u64::try_from(amount).ok());
+        match (amount_a, amount_b) {
+            (Some(amount_a), Some(amount_b)) => Ok((amount_a, amount_b)),
+            _ => err!(AmmError::InvalidAmount),
+        }
+    }
+
+    fn transfer_to_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let (token_program, from, mint, to) = if is_token_a {
+            (&self.token_program_a, &self.owner_account_a, &self.mint_a, &self.pool_account_a)
+        } else {
+            (&self.token_program_b, &self.owner_account_b, &self.mint_b, &self.pool_account_b)
+        };
+
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.owner.to_account_info(),
+            remaining_accounts,
+            amount,
+            mint.decimals,
+            &[],
+        )
+    }
+
+    fn transfer_from_pool(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let pool_key = self.pool.key();
+        let mint_a_key = self.mint_a.key();
+        let mint_b_key = self.mint_b.key();
+        let authority_seeds = &[
+            pool_key.as_ref(),
+            mint_a_key.as_ref(),
+            mint_b_key.as_ref(),
+            POOL_AUTHORITY_SEED,
+            &[self.pool.pool_authority_bump],
+        ];
+
+        let (token_program, from, mint, to) = if is_token_a {
+            (&self.token_program_a, &self.pool_account_a, &self.mint_a, &self.owner_account_a)
+        } else {
+            (&self.token_program_b, &self.pool_account_b, &self.mint_b, &self.owner_account_b)
+        };
+
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.pool_authority.to_account_info(),
+            remaining_accounts,
+            amount,
+            mint.decimals,
+            &[&authority_seeds[..]],
+        )
+    }
+}
+
+/// Adds `delta` to a position boundary; `upper` ticks remove the liquidity when crossed upwards
+fn update_tick(tick_arrays: &mut TickArrays, pool: &Pool, tick_index: i32, delta: i128, upper: bool) -> Result<()> {
+    let tick = tick_arrays.tick_mut(tick_index)?;
+    let liquidity_gross = add_liquidity_delta(tick.liquidity_gross, delta)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+    // By convention all fees so far were earned below a newly initialized tick
+    if tick.liquidity_gross == 0 && tick_index <= pool.tick_current {
+        tick.fee_growth_outside_a_x64 = pool.fee_growth_global_a_x64;
+        tick.fee_growth_outside_b_x64 = pool.fee_growth_global_b_x64;
+    }
+
+    tick.liquidity_gross = liquidity_gross;
+    tick.liquidity_net = if upper {
+        tick.liquidity_net.checked_sub(delta)
+    } else {
+        tick.liquidity_net.checked_add(delta)
+    }.ok_or_else(|| error!(AmmError::InvalidAmount))?;
+    Ok(())
+}
+
+/// LP fee growth per unit of liquidity inside [`tick_lower`, `tick_upper`), (A, B)
+fn fee_growth_inside(
+    tick_arrays: &mut TickArrays,
+    pool: &Pool,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(u128, u128)> {
+    let lower = tick_arrays.tick(tick_lower)?.ok_or_else(|| error!(AmmError::TickArrayMissing))?;
+    let upper = tick_arrays.tick(tick_upper)?.ok_or_else(|| error!(AmmError::TickArrayMissing))?;
+
+    // Growth below the lower tick and above the upper tick, from whichever side the price is on
+    let inside = |global: u128, lower_outside: u128, upper_outside: u128| {
+        let below = if pool.tick_current >= tick_lower {
+            lower_outside
+        } else {
+            global.wrapping_sub(lower_outside)
+        };
+        let above = if pool.tick_current < tick_upper {
+            upper_outside
+        } else {
+            global.wrapping_sub(upper_outside)
+        };
+        global.wrapping_sub(below).wrapping_sub(above)
+    };
+
+    Ok((
+        inside(pool.fee_growth_global_a_x64, lower.fee_growth_outside_a_x64, upper.fee_growth_outside_a_x64),
+        inside(pool.fee_growth_global_b_x64, lower.fee_growth_outside_b_x64, upper.fee_growth_outside_b_x64),
+    ))
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::{
+    constants::{AMM_SEED, MAX_AMP, MAX_AMP_CHANGE, MIN_AMP, MIN_AMP_RAMP_DURATION},
+    errors::AmmError,
+    events::AmplificationRampStarted,
+    state::{Amm, CurveType, Pool},
+};
+
+#[derive(Accounts)]
+#[instruction(target_amp: u64, ramp_end: i64)]
+pub struct RampAmplification<'info> {
+    #[account(
+        seeds = [
+            AMM_SEED,
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = admin @ AmmError::UnauthorizedAdmin,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> RampAmplification<'info> {
+    /// Ramps are not routed through the `ParamChange` timelock: amp never jumps, it moves
+    /// by at most `MAX_AMP_CHANGE` over at least `MIN_AMP_RAMP_DURATION`, so LPs can
+    /// watch a ramp on chain and leave long before it has moved the curve far.
+    pub fn ramp_amplification(&mut self, target_amp: u64, ramp_end: i64) -> Result<()> {
+        // An immutable AMM's pools keep the curve they have
+        require!(!self.amm.is_immutable, AmmError::UnauthorizedAdmin);
+        require!(self.pool.curve_type == CurveType::StableSwap, AmmError::InvalidCurve);
+        require!((MIN_AMP..=MAX_AMP).contains(&target_amp), AmmError::InvalidAmplification);
+
+        // Slow, bounded ramps keep LPs from being drained by a sudden change in price
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ramp_end >= now.saturating_add(MIN_AMP_RAMP_DURATION),
+            AmmError::InvalidAmplification
+        );
+        let current_amp = self.pool.amplification(now);
+        require!(
+            target_amp <= current_amp.saturating_mul(MAX_AMP_CHANGE)
+                && current_amp <= target_amp.saturating_mul(MAX_AMP_CHANGE),
+            AmmError::InvalidAmplification
+        );
+
+        // A new ramp starts wherever the current one has got to
+        self.pool.amp_initial = current_amp;
+        self.pool.amp_target = target_amp;
+        self.pool.amp_ramp_start = now;
+        self.pool.amp_ramp_end = ramp_end;
+
+        msg!("Ramping amplification from {} to {} until {}", current_amp, target_amp, ramp_end);
+        emit!(AmplificationRampStarted {
+            pool: self.pool.key(),
+            amp_initial: current_amp,
+            amp_target: target_amp,
+            ramp_start: now,
+            ramp_end,
+        });
+        Ok(())
+    }
+}
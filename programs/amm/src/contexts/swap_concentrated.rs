@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_TICK, MIN_TICK},
+    contexts::{
+        tick_array::TickArrays,
+        tick_math::{
+            add_liquidity_delta,
+            compute_swap_step,
+            sqrt_price_at_tick,
+            tick_at_sqrt_price,
+            MAX_SQRT_PRICE_X64,
+            MIN_SQRT_PRICE_X64,
+            Q64,
+        },
+        utils::{calculate_protocol_fee, mul_div},
+        Swap,
+    },
+    errors::*,
+};
+
+/// Totals of a swap through a concentrated pool's ticks
+pub(crate) struct ConcentratedSwap {
+    /// Input taken by the pool, including the trade fee
+    pub amount_in: u64,
+    /// Output paid by the pool
+    pub amount_out: u64,
+    /// LP trading fee, in input token units
+    pub trade_fee: u64,
+    /// Part of the trade fee set aside for the protocol, in input token units
+    pub protocol_fee: u64,
+}
+
+// Concentrated pools reuse the `Swap` accounts and settlement; only the pricing walks
+// the pool's ticks instead of reading the vault reserves.
+impl<'info> Swap<'info> {
+    /// Moves the pool's price through its initialized ticks until `amount` (input when
+    /// `exact_input`, otherwise output) is used up, crediting the LP fee of every step to
+    /// the liquidity active during it. Tick arrays are passed as remaining accounts.
+    pub(crate) fn swap_concentrated(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        swap_a: bool,
+        amount: u64,
+        exact_input: bool,
+    ) -> Result<ConcentratedSwap> {
+        require!(amount > 0, AmmError::InvalidAmount);
+        let protocol_fee_share_bps = self.amm.protocol_fee_share_bps;
+        let mut tick_arrays = TickArrays::new(self.pool.key(), self.pool.tick_spacing, remaining_accounts);
+        let pool = &mut self.pool;
+
+        let mut amount_remaining = amount;
+        let mut amount_calculated: u64 = 0;
+        let mut trade_fee: u64 = 0;
+        let mut protocol_fee: u64 = 0;
+        while amount_remaining > 0 {
+            // Running into the end of the price range means the pool cannot fill the swap
+            let at_price_limit = if swap_a {
+                pool.sqrt_price_x64 <= MIN_SQRT_PRICE_X64
+            } else {
+                pool.sqrt_price_x64 >= MAX_SQRT_PRICE_X64
+            };
+            require!(!at_price_limit, AmmError::InsufficientLiquidity);
+
+            let (next_tick, initialized) = tick_arrays.next_initialized_tick(pool.tick_current, swap_a)?;
+            let next_tick = next_tick.clamp(MIN_TICK, MAX_TICK);
+            let sqrt_price_target = sqrt_price_at_tick(next_tick)
+                .ok_or_else(|| error!(AmmError::InvalidTickRange))?;
+
+            let step = compute_swap_step(
+                pool.sqrt_price_x64,
+                sqrt_price_target,
+                pool.liquidity,
+                amount_remaining,
+                pool.trade_fee_bps,
+                exact_input,
+            ).ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+            let step_input = step.amount_in
+                .checked_add(step.fee_amount)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            let (used, calculated) = if exact_input {
+                (step_input, step.amount_out)
+            } else {
+                (step.amount_out, step_input)
+            };
+            amount_remaining = amount_remaining
+                .checked_sub(used)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            amount_calculated = amount_calculated
+                .checked_add(calculated)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+            // The protocol's cut comes off each step's fee, the rest accrues to active liquidity
+            let step_protocol_fee = calculate_protocol_fee(step.fee_amount, protocol_fee_share_bps)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            trade_fee = trade_fee
+                .checked_add(step.fee_amount)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            protocol_fee = protocol_fee
+                .checked_add(step_protocol_fee)
+                .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+            if pool.liquidity > 0 {
+                let fee_growth = mul_div((step.fee_amount - step_protocol_fee) as u128, Q64, pool.liquidity)
+                    .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+                let fee_growth_global = if swap_a {
+                    &mut pool.fee_growth_global_a_x64
+                } else {
+                    &mut pool.fee_growth_global_b_x64
+                };
+                *fee_growth_global = fee_growth_global.wrapping_add(fee_growth);
+            }
+
+            pool.sqrt_price_x64 = step.sqrt_price_next;
+            if step.sqrt_price_next == sqrt_price_target {
+                // The upward search stops short of the next array's first tick, so read it
+                // only now that the price has reached it
+                let initialized = initialized
+                    || (!swap_a && tick_arrays.tick(next_tick)?.is_some_and(|tick| tick.liquidity_gross > 0));
+
+                // Crossing a tick flips its outside fee growth and adds or removes the
+                // liquidity of the positions it bounds
+                if initialized {
+                    let tick = tick_arrays.tick_mut(next_tick)?;
+                    tick.fee_growth_outside_a_x64 = pool.fee_growth_global_a_x64.wrapping_sub(tick.fee_growth_outside_a_x64);
+                    tick.fee_growth_outside_b_x64 = pool.fee_growth_global_b_x64.wrapping_sub(tick.fee_growth_outside_b_x64);
+                    let liquidity_net = if swap_a { -tick.liquidity_net } else { tick.liquidity_net };
+                    pool.liquidity = add_liquidity_delta(pool.liquidity, liquidity_net)
+                        .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+                }
+                pool.tick_current = if swap_a { next_tick - 1 } else { next_tick };
+            } else {
+                pool.tick_current = tick_at_sqrt_price(pool.sqrt_price_x64)
+                    .ok_or_else(|| error!(AmmError::InvalidSqrtPrice))?;
+            }
+        }
+        tick_arrays.exit()?;
+
+        let (amount_in, amount_out) = if exact_input {
+            (amount, amount_calculated)
+        } else {
+            (amount_calculated, amount)
+        };
+        msg!(
+            "Concentrated swap: {} in, {} out, price {} (tick {})",
+            amount_in,
+            amount_out,
+            pool.sqrt_price_x64,
+            pool.tick_current
+        );
+
+        Ok(ConcentratedSwap {
+            amount_in,
+            amount_out,
+            trade_fee,
+            protocol_fee,
+        })
+    }
+}
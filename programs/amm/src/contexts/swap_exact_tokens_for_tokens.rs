@@ -1,17 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{Token2022, Mint},
+    token_interface::{TokenInterface, Mint},
 };
-use anchor_spl::token_interface::spl_token_2022;
-use anchor_lang::solana_program::instruction::AccountMeta;
-use anchor_lang::solana_program::program::{invoke, invoke_signed};
-use anchor_lang::solana_program::sysvar;
 use anchor_spl::token_interface::TokenAccount;
 use crate::{
-    constants::{POOL_AUTHORITY_SEED, AMM_SEED},
+    constants::{POOL_AUTHORITY_SEED, AMM_SEED, OBSERVATIONS_SEED},
+    contexts::oracle::update_oracle,
+    contexts::transfers::transfer_checked_with_hook,
+    contexts::utils::{calculate_protocol_fee, calculate_swap_output, calculate_trade_fee, get_transfer_fee, SwapSide},
     errors::*,
-    state::{Amm, Pool},
+    events::Swapped,
+    state::{Amm, CurveType, Observations, Pool},
 };
 
 #[derive(Accounts)]
@@ -28,15 +29,25 @@ pub struct Swap<'info> {
     pub amm: Box<Account<'info, Amm>>,
 
     #[account(
+        mut,
         seeds = [
             amm.key().as_ref(),
             mint_a.key().as_ref(),
             mint_b.key().as_ref(),
         ],
         bump,
+        constraint = !pool.locked @ AmmError::PoolLocked,
+        constraint = !pool.is_paused(&amm) @ AmmError::Paused,
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
     /// CHECK: Pool authority PDA
     #[account(
         seeds = [
@@ -55,71 +66,79 @@ pub struct Swap<'info> {
     #[account(mut,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_a,
     )]
     pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_b,
     )]
     pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut,
         associated_token::mint = mint_a,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_a,
     )]
     pub user_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut,
         associated_token::mint = mint_b,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = token_program_b,
     )]
     pub user_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
+        mut,
         constraint = user.is_signer @ AmmError::NotSigner
     )]
     pub user: Signer<'info>,
 
+    /// CHECK: Receives the SOL fee, must match the collector configured on the AMM
+    #[account(
+        mut,
+        address = amm.sol_fee_collector @ AmmError::InvalidFeeCollector
+    )]
+    pub sol_fee_collector: AccountInfo<'info>,
+
     /// Solana ecosystem accounts
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token2022>,
-
-    /// Transfer hook accounts for mint A
-    /// CHECK: Extra account meta list for mint A
-    pub extra_account_meta_list_a: AccountInfo<'info>,
-
-    /// CHECK: Mint trade counter for mint A
-    #[account(mut)]
-    pub mint_trade_counter_a: AccountInfo<'info>,
-
-    /// Transfer hook accounts for mint B
-    /// CHECK: Extra account meta list for mint B
-    pub extra_account_meta_list_b: AccountInfo<'info>,
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
 
-    /// CHECK: Mint trade counter for mint B
-    #[account(mut)]
-    pub mint_trade_counter_b: AccountInfo<'info>,
-
-    /// CHECK: Transfer hook program for mint A
-    pub transfer_hook_program_a: AccountInfo<'info>,
-
-    /// CHECK: Transfer hook program for mint B
-    pub transfer_hook_program_b: AccountInfo<'info>,
+    // Transfer hook accounts of whichever mints have a hook, and the tick arrays the
+    // swap crosses on a concentrated pool, are passed as remaining accounts
+}
 
-    /// CHECK: Instructions sysvar
-    #[account(address = sysvar::instructions::ID)]
-    pub instructions_sysvar: AccountInfo<'info>,
+/// Amounts of a quoted swap. Each side only differs from the other when its mint
+/// charges a Token-2022 transfer fee.
+pub(crate) struct SwapAmounts {
+    /// Sent by the user
+    pub input_amount: u64,
+    /// Arrives in the input vault; this is what the curve and the trade fee see
+    pub pool_input_amount: u64,
+    /// Sent out of the output vault
+    pub pool_output_amount: u64,
+    /// Arrives in the user's account
+    pub output_amount: u64,
+    /// LP trading fee, in input token units
+    pub trade_fee: u64,
+    /// Part of the trade fee set aside for the protocol, in input token units
+    pub protocol_fee: u64,
 }
 
 impl<'info> Swap<'info> {
     pub fn swap(
         &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
         swap_a: bool,
         input_amount: u64,
         min_output_amount: u64,
@@ -127,29 +146,116 @@ impl<'info> Swap<'info> {
     ) -> Result<()> {
         msg!("Swapping {} tokens", input_amount);
         msg!("Swap direction: {}", if swap_a { "A to B" } else { "B to A" });
+        self.update_oracle()?;
 
-        // Calculate output amount (simplified constant product formula)
-        let output_amount = self.calculate_output_amount(swap_a, input_amount)?;
+        // The vault only receives what is left after the input mint's transfer fee
+        let (mint_in, mint_out) = self.mints_in_out(swap_a);
+        let pool_input_amount = input_amount
+            .checked_sub(get_transfer_fee(&mint_in, input_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        
+        // Price the swap on the pool's curve, or walk the ticks of a concentrated pool
+        let (pool_output_amount, trade_fee, protocol_fee) = if self.pool.curve_type == CurveType::Concentrated {
+            let swap = self.swap_concentrated(remaining_accounts, swap_a, pool_input_amount, true)?;
+            (swap.amount_out, swap.trade_fee, swap.protocol_fee)
+        } else {
+            let pool_output_amount = self.calculate_output_amount(swap_a, pool_input_amount)?;
+            let (trade_fee, protocol_fee) = self.calculate_fees(pool_input_amount)?;
+            (pool_output_amount, trade_fee, protocol_fee)
+        };
+        
+        // The user only receives what is left after the output mint's transfer fee
+        let output_amount = pool_output_amount
+            .checked_sub(get_transfer_fee(&mint_out, pool_output_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
         
-        // Check slippage
+        // Check slippage against what actually lands in the user's account
         require!(output_amount >= min_output_amount, AmmError::SlippageExceeded);
         
-        // Execute the swap
-        self.execute_swap(swap_a, input_amount, output_amount)?;
+        self.settle_swap(remaining_accounts, swap_a, SwapAmounts {
+            input_amount,
+            pool_input_amount,
+            pool_output_amount,
+            output_amount,
+            trade_fee,
+            protocol_fee,
+        })
+    }
+    
+    /// (trade fee, protocol fee) on the input that reaches the pool
+    pub(crate) fn calculate_fees(&self, pool_input_amount: u64) -> Result<(u64, u64)> {
+        calculate_swap_fees(&self.amm, &self.pool, pool_input_amount)
+    }
+    
+    /// Accrues the TWAP accumulators on the pre-trade reserves
+    pub(crate) fn update_oracle(&mut self) -> Result<()> {
+        update_oracle(
+            &mut self.pool,
+            &mut self.observations,
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        )
+    }
+    
+    /// Returns (input mint, output mint) for the given direction
+    pub(crate) fn mints_in_out(&self, swap_a: bool) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        if swap_a {
+            (self.mint_a.to_account_info(), self.mint_b.to_account_info())
+        } else {
+            (self.mint_b.to_account_info(), self.mint_a.to_account_info())
+        }
+    }
+    
+    /// Takes the fees, moves the tokens and reports the swap once both amounts are fixed.
+    /// Shared by the exact-input and exact-output entry points.
+    pub(crate) fn settle_swap(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        swap_a: bool,
+        amounts: SwapAmounts,
+    ) -> Result<()> {
+        let (input_amount, output_amount) = (amounts.input_amount, amounts.output_amount);
+        let side_a = SettleSide {
+            mint: &self.mint_a,
+            vault: &mut self.pool_account_a,
+            user_account: self.user_account_a.to_account_info(),
+            token_program: self.token_program_a.to_account_info(),
+        };
+        let side_b = SettleSide {
+            mint: &self.mint_b,
+            vault: &mut self.pool_account_b,
+            user_account: self.user_account_b.to_account_info(),
+            token_program: self.token_program_b.to_account_info(),
+        };
+        let (input, output) = if swap_a { (side_a, side_b) } else { (side_b, side_a) };
         
-        msg!("Swap completed successfully. Output: {}", output_amount);
+        settle_pool_swap(
+            SettleAccounts {
+                amm: &self.amm,
+                pool: &mut self.pool,
+                pool_authority: self.pool_authority.to_account_info(),
+                user: self.user.to_account_info(),
+                sol_fee_collector: self.sol_fee_collector.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                input,
+                output,
+            },
+            remaining_accounts,
+            swap_a,
+            amounts,
+        )?;
+        
+        msg!("Swap completed successfully. Input: {}, Output: {}", input_amount, output_amount);
         Ok(())
     }
     
+    /// Returns (reserve_in, reserve_out) for the given direction, leaving out uncollected protocol fees
+    pub(crate) fn reserves_in_out(&self, swap_a: bool) -> Result<(u64, u64)> {
+        let (reserve_a, reserve_b) = self.pool.reserves(
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        );
 
-        
-    fn calculate_output_amount(&self, swap_a: bool, input_amount: u64) -> Result<u64> {
-        // Get current reserves from pool accounts as u128 to avoid overflow
-        let reserve_a = self.pool_account_a.amount as u128;
-        let reserve_b = self.pool_account_b.amount as u128;
-
-        // Simplified constant product formula: (x + dx) * (y - dy) = x * y
-        // where dx is input_amount and dy is output_amount
         let (reserve_in, reserve_out) = if swap_a {
             (reserve_a, reserve_b)
         } else {
@@ -158,191 +264,182 @@ impl<'info> Swap<'info> {
 
         // Guard against empty reserves
         require!(reserve_in > 0 && reserve_out > 0, AmmError::InsufficientLiquidity);
+        Ok((reserve_in, reserve_out))
+    }
+    
+    /// Input and output sides of the pool as the curve sees them
+    pub(crate) fn swap_sides(&self, swap_a: bool) -> Result<(SwapSide, SwapSide)> {
+        let (reserve_in, reserve_out) = self.reserves_in_out(swap_a)?;
+        let (weight_in, weight_out) = self.pool.weights_in_out(swap_a);
+        let (decimals_in, decimals_out) = if swap_a {
+            (self.mint_a.decimals, self.mint_b.decimals)
+        } else {
+            (self.mint_b.decimals, self.mint_a.decimals)
+        };
 
-        let input = input_amount as u128;
-        let numerator = input.checked_mul(reserve_out).ok_or_else(|| error!(AmmError::InvalidAmount))?;
-        let denominator = reserve_in.checked_add(input).ok_or_else(|| error!(AmmError::InvalidAmount))?;
-        let output_u128 = numerator.checked_div(denominator).ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        Ok((
+            SwapSide { reserve: reserve_in, decimals: decimals_in, weight: weight_in },
+            SwapSide { reserve: reserve_out, decimals: decimals_out, weight: weight_out },
+        ))
+    }
 
-        // Ensure result fits in u64
-        if output_u128 > u64::MAX as u128 {
-            return Err(error!(AmmError::InvalidAmount));
-        }
-        Ok(output_u128 as u64)
+    fn calculate_output_amount(&self, swap_a: bool, input_amount: u64) -> Result<u64> {
+        let (input, output) = self.swap_sides(swap_a)?;
+
+        // Price on the pool's curve with the LP trading fee taken off the input
+        calculate_swap_output(
+            &self.pool,
+            Clock::get()?.unix_timestamp,
+            input_amount,
+            input,
+            output,
+        ).ok_or_else(|| error!(AmmError::InvalidAmount))
     }
-    
-    fn execute_swap(&self, swap_a: bool, input_amount: u64, output_amount: u64) -> Result<()> {
+}
+
+/// (trade fee, protocol fee) a pool charges on the input that reaches it
+pub(crate) fn calculate_swap_fees(amm: &Amm, pool: &Pool, pool_input_amount: u64) -> Result<(u64, u64)> {
+    let trade_fee = calculate_trade_fee(pool_input_amount, pool.trade_fee_bps)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+    let protocol_fee = calculate_protocol_fee(trade_fee, amm.protocol_fee_share_bps)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+    Ok((trade_fee, protocol_fee))
+}
+
+/// One side of a swap: the mint, the pool's vault for it and the user's account for it
+pub(crate) struct SettleSide<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub user_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Accounts of the pool a swap settles against, already checked by the caller
+pub(crate) struct SettleAccounts<'a, 'info> {
+    pub amm: &'a Account<'info, Amm>,
+    pub pool: &'a mut Account<'info, Pool>,
+    pub pool_authority: AccountInfo<'info>,
+    pub user: AccountInfo<'info>,
+    pub sol_fee_collector: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub input: SettleSide<'a, 'info>,
+    pub output: SettleSide<'a, 'info>,
+}
+
+/// Settles a quoted swap against one pool: accrues the protocol fee, charges the AMM's
+/// SOL fee, moves both tokens and emits `Swapped`. Used by single swaps and by every hop
+/// of a route. The caller persists the pool.
+pub(crate) fn settle_pool_swap<'info>(
+    accounts: SettleAccounts<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    swap_a: bool,
+    amounts: SwapAmounts,
+) -> Result<()> {
+    let SettleAccounts {
+        amm,
+        pool,
+        pool_authority,
+        user,
+        sol_fee_collector,
+        system_program,
+        input,
+        output,
+    } = accounts;
+    msg!("Trade fee: {} ({} bps)", amounts.trade_fee, pool.trade_fee_bps);
+    let reserves = |pool: &Pool, input: &SettleSide, output: &SettleSide| {
         if swap_a {
-            // Swap A to B: user sends A to pool, pool sends B to user
-            self.transfer_from_user_to_pool(input_amount, true)?;
-            self.transfer_from_pool_to_user(output_amount, false)?;
+            pool.reserves(input.vault.amount, output.vault.amount)
         } else {
-            // Swap B to A: user sends B to pool, pool sends A to user
-            self.transfer_from_user_to_pool(input_amount, false)?;
-            self.transfer_from_pool_to_user(output_amount, true)?;
+            pool.reserves(output.vault.amount, input.vault.amount)
         }
-        
-        Ok(())
-    }
+    };
+    let (reserve_a_before, reserve_b_before) = reserves(pool, &input, &output);
     
-    fn transfer_from_user_to_pool(&self, amount: u64, is_token_a: bool) -> Result<()> {
-        if is_token_a {
-            // Transfer token A with transfer hook
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.user_account_a.key(),
-                &self.mint_a.key(),
-                &self.pool_account_a.key(),
-                &self.user.key(),
-                &[&self.user.key()],
-                amount,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint A
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-            
-            // Invoke the modified instruction
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.user_account_a.to_account_info(),
-                self.mint_a.to_account_info(),
-                self.pool_account_a.to_account_info(),
-                self.user.to_account_info(),
-                self.extra_account_meta_list_a.to_account_info(),
-                self.mint_trade_counter_a.to_account_info(),
-                self.transfer_hook_program_a.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            invoke(&transfer_ix, account_infos)?;
-        } else {
-            // Transfer token B with transfer hook
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.user_account_b.key(),
-                &self.mint_b.key(),
-                &self.pool_account_b.key(),
-                &self.user.key(),
-                &[&self.user.key()],
-                amount,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint B
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-            
-            // Invoke the modified instruction
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.user_account_b.to_account_info(),
-                self.mint_b.to_account_info(),
-                self.pool_account_b.to_account_info(),
-                self.user.to_account_info(),
-                self.extra_account_meta_list_b.to_account_info(),
-                self.mint_trade_counter_b.to_account_info(),
-                self.transfer_hook_program_b.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            invoke(&transfer_ix, account_infos)?;
-        }
-        
-        Ok(())
-    }
+    // Set aside the protocol's cut of the trade fee; it stays in the vault until collected.
+    // The fee is taken from the input token, which is A when swapping A to B
+    let accrued = if swap_a {
+        &mut pool.protocol_fees_a
+    } else {
+        &mut pool.protocol_fees_b
+    };
+    *accrued = accrued
+        .checked_add(amounts.protocol_fee)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+    msg!("Protocol fee: {}", amounts.protocol_fee);
     
-    fn transfer_from_pool_to_user(&self, amount: u64, is_token_a: bool) -> Result<()> {
-        // Get pool authority bump from pool state
-        let pool_authority_bump = self.pool.pool_authority_bump;
-        
-        let pool_key = self.pool.key();
-        let mint_a_key = self.mint_a.key();
-        let mint_b_key = self.mint_b.key();
-        
-        // Create the signer seeds for the pool authority PDA
-        let authority_seeds = &[
-            pool_key.as_ref(),
-            mint_a_key.as_ref(),
-            mint_b_key.as_ref(),
-            POOL_AUTHORITY_SEED,
-            &[pool_authority_bump],
-        ];
-        let signer_seeds = &[&authority_seeds[..]];
-        
-        if is_token_a {
-            // Transfer token A from pool to user with transfer hook
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.pool_account_a.key(),
-                &self.mint_a.key(),
-                &self.user_account_a.key(),
-                &self.pool_authority.key(),
-                &[],
-                amount,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint A
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_a.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-            
-            // Invoke the modified instruction with PDA signing
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.pool_account_a.to_account_info(),
-                self.mint_a.to_account_info(),
-                self.user_account_a.to_account_info(),
-                self.pool_authority.to_account_info(),
-                self.extra_account_meta_list_a.to_account_info(),
-                self.mint_trade_counter_a.to_account_info(),
-                self.transfer_hook_program_a.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            invoke_signed(&transfer_ix, account_infos, &[&authority_seeds[..]])?;
-        } else {
-            // Transfer token B from pool to user with transfer hook
-            let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
-                &spl_token_2022::id(),
-                &self.pool_account_b.key(),
-                &self.mint_b.key(),
-                &self.user_account_b.key(),
-                &self.pool_authority.key(),
-                &[],
-                amount,
-                6,
-            ).unwrap();
-            
-            // Add transfer hook accounts for mint B
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.extra_account_meta_list_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new(self.mint_trade_counter_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(self.transfer_hook_program_b.key(), false));
-            transfer_ix.accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
-            
-            // Invoke the modified instruction with PDA signing
-            let account_infos = &[
-                self.token_program.to_account_info(),
-                self.pool_account_b.to_account_info(),
-                self.mint_b.to_account_info(),
-                self.user_account_b.to_account_info(),
-                self.pool_authority.to_account_info(),
-                self.extra_account_meta_list_b.to_account_info(),
-                self.mint_trade_counter_b.to_account_info(),
-                self.transfer_hook_program_b.to_account_info(),
-                self.instructions_sysvar.to_account_info(),
-            ];
-            
-            invoke_signed(&transfer_ix, account_infos, &[&authority_seeds[..]])?;
-        }
-        
-        Ok(())
+    // Charge the AMM's SOL fee
+    let sol_fee = amm.sol_fee;
+    if sol_fee > 0 {
+        transfer(
+            CpiContext::new(
+                system_program,
+                Transfer {
+                    from: user.clone(),
+                    to: sol_fee_collector.clone(),
+                },
+            ),
+            sol_fee,
+        )?;
+        msg!("SOL fee: {} lamports to {}", sol_fee, sol_fee_collector.key());
     }
-} 
\ No newline at end of file
+    
+    // User sends the input token to the pool
+    transfer_checked_with_hook(
+        &input.token_program,
+        &input.user_account,
+        &input.mint.to_account_info(),
+        &input.vault.to_account_info(),
+        &user,
+        remaining_accounts,
+        amounts.input_amount,
+        input.mint.decimals,
+        &[],
+    )?;
+    
+    // Pool sends the output token to the user, signed by the pool authority
+    let pool_key = pool.key();
+    let authority_seeds = &[
+        pool_key.as_ref(),
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        POOL_AUTHORITY_SEED,
+        &[pool.pool_authority_bump],
+    ];
+    transfer_checked_with_hook(
+        &output.token_program,
+        &output.vault.to_account_info(),
+        &output.mint.to_account_info(),
+        &output.user_account,
+        &pool_authority,
+        remaining_accounts,
+        amounts.pool_output_amount,
+        output.mint.decimals,
+        &[&authority_seeds[..]],
+    )?;
+    
+    // Read the vaults back for the post-trade reserves
+    input.vault.reload()?;
+    output.vault.reload()?;
+    let (reserve_a_after, reserve_b_after) = reserves(pool, &input, &output);
+    
+    emit!(Swapped {
+        amm: amm.key(),
+        pool: pool_key,
+        user: user.key(),
+        swap_a,
+        input_amount: amounts.input_amount,
+        output_amount: amounts.output_amount,
+        input_transfer_fee: amounts.input_amount - amounts.pool_input_amount,
+        output_transfer_fee: amounts.pool_output_amount - amounts.output_amount,
+        trade_fee: amounts.trade_fee,
+        protocol_fee: amounts.protocol_fee,
+        sol_fee,
+        sol_fee_collector: sol_fee_collector.key(),
+        reserve_a_before,
+        reserve_b_before,
+        reserve_a_after,
+        reserve_b_after,
+    });
+    
+    Ok(())
+}
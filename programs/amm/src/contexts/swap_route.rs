@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, Mint};
+use crate::{
+    constants::{MAX_ROUTE_HOPS, POOL_AUTHORITY_SEED},
+    contexts::oracle::update_oracle,
+    contexts::swap_exact_tokens_for_tokens::{calculate_swap_fees, settle_pool_swap, SettleAccounts, SettleSide, SwapAmounts},
+    contexts::utils::{calculate_swap_output, get_transfer_fee, SwapSide},
+    errors::*,
+    state::{Amm, CurveType, Observations, Pool},
+};
+
+/// Number of `remaining_accounts` each hop takes, in this order:
+///  0. AMM of the pool
+///  1. pool (writable)
+///  2. pool authority
+///  3. input mint
+///  4. output mint
+///  5. pool vault for the input mint (writable)
+///  6. pool vault for the output mint (writable)
+///  7. user token account for the input mint (writable)
+///  8. user token account for the output mint (writable)
+///  9. SOL fee collector of the AMM (writable)
+/// 10. token program of the input mint
+/// 11. token program of the output mint
+/// 12. TWAP observations of the pool (writable)
+///
+/// After the last hop come the transfer hook accounts of every hooked mint on the
+/// route (hook program, `ExtraAccountMetaList` and whatever it resolves to), in any
+/// order. Mints without a hook need nothing there.
+///
+/// Every hop pays the SOL fee of its own AMM, so a route costs what the same swaps
+/// made one by one would. Each pair has its own AMM, admin and fee collector, and
+/// charging only one of them would let a route skip the others' fees.
+pub const ROUTE_HOP_ACCOUNTS: usize = 13;
+
+#[derive(Accounts)]
+#[instruction(hops: u8, amount_in: u64, min_output_amount: u64)]
+pub struct SwapRoute<'info> {
+    #[account(
+        mut,
+        constraint = user.is_signer @ AmmError::NotSigner
+    )]
+    pub user: Signer<'info>,
+
+    /// Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SwapRoute<'info> {
+    pub fn swap_route(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        hops: u8,
+        amount_in: u64,
+        min_output_amount: u64,
+    ) -> Result<()> {
+        require!(amount_in > 0, AmmError::InvalidAmount);
+        let hops = hops as usize;
+        require!(
+            hops > 0 && hops <= MAX_ROUTE_HOPS && hops * ROUTE_HOP_ACCOUNTS <= remaining_accounts.len(),
+            AmmError::InvalidRoute
+        );
+        msg!("Routing {} tokens through {} pools", amount_in, hops);
+
+        let (route_accounts, hook_accounts) = remaining_accounts.split_at(hops * ROUTE_HOP_ACCOUNTS);
+        let mut amount = amount_in;
+        let mut previous_output_account: Option<Pubkey> = None;
+        for (hop, hop_accounts) in route_accounts.chunks(ROUTE_HOP_ACCOUNTS).enumerate() {
+            // Each hop spends exactly what the previous one paid into the user's account
+            if let Some(previous_output_account) = previous_output_account {
+                require_keys_eq!(hop_accounts[7].key(), previous_output_account, AmmError::InvalidRoute);
+            }
+
+            amount = self.swap_hop(hop, hop_accounts, hook_accounts, amount)?;
+            previous_output_account = Some(hop_accounts[8].key());
+        }
+
+        // Check slippage on the final output only
+        require!(amount >= min_output_amount, AmmError::SlippageExceeded);
+
+        msg!("Route completed successfully. Output: {}", amount);
+        Ok(())
+    }
+
+    fn swap_hop(
+        &self,
+        hop: usize,
+        accounts: &'info [AccountInfo<'info>],
+        hook_accounts: &'info [AccountInfo<'info>],
+        input_amount: u64,
+    ) -> Result<u64> {
+        let amm: Account<'info, Amm> = Account::try_from(&accounts[0])?;
+        let mut pool: Account<'info, Pool> = Account::try_from(&accounts[1])?;
+        require_keys_eq!(pool.amm, amm.key(), AmmError::InvalidPool);
+        // Concentrated pools need tick arrays, which the fixed hop layout has no room for
+        require!(pool.curve_type != CurveType::Concentrated, AmmError::InvalidCurve);
+        require!(!pool.locked, AmmError::PoolLocked);
+        require!(!pool.is_paused(&amm), AmmError::Paused);
+
+        // Verify the pool authority derivation
+        let pool_key = pool.key();
+        let expected_pool_authority = Pubkey::create_program_address(
+            &[
+                pool_key.as_ref(),
+                pool.mint_a.as_ref(),
+                pool.mint_b.as_ref(),
+                POOL_AUTHORITY_SEED,
+                &[pool.pool_authority_bump],
+            ],
+            &crate::ID,
+        ).map_err(|_| AmmError::InvalidPoolAuthority)?;
+        let pool_authority = &accounts[2];
+        require_keys_eq!(pool_authority.key(), expected_pool_authority, AmmError::InvalidPoolAuthority);
+
+        // The mints decide the direction of this hop
+        let mint_in: InterfaceAccount<'info, Mint> = InterfaceAccount::try_from(&accounts[3])?;
+        let mint_out: InterfaceAccount<'info, Mint> = InterfaceAccount::try_from(&accounts[4])?;
+        let swap_a = if mint_in.key() == pool.mint_a && mint_out.key() == pool.mint_b {
+            true
+        } else if mint_in.key() == pool.mint_b && mint_out.key() == pool.mint_a {
+            false
+        } else {
+            return err!(AmmError::InvalidMint);
+        };
+
+        let (expected_vault_in, expected_vault_out, expected_program_in, expected_program_out) = if swap_a {
+            (pool.vault_a, pool.vault_b, pool.token_program_a, pool.token_program_b)
+        } else {
+            (pool.vault_b, pool.vault_a, pool.token_program_b, pool.token_program_a)
+        };
+        require_keys_eq!(accounts[5].key(), expected_vault_in, AmmError::InvalidPool);
+        require_keys_eq!(accounts[6].key(), expected_vault_out, AmmError::InvalidPool);
+        let token_program_in = &accounts[10];
+        let token_program_out = &accounts[11];
+        require_keys_eq!(token_program_in.key(), expected_program_in, AmmError::InvalidTokenProgram);
+        require_keys_eq!(token_program_out.key(), expected_program_out, AmmError::InvalidTokenProgram);
+        let mut vault_in: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&accounts[5])?;
+        let mut vault_out: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&accounts[6])?;
+
+        let user_in: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&accounts[7])?;
+        let user_out: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&accounts[8])?;
+        require_keys_eq!(user_in.owner, self.user.key(), AmmError::InvalidRoute);
+        require_keys_eq!(user_out.owner, self.user.key(), AmmError::InvalidRoute);
+        require_keys_eq!(user_in.mint, mint_in.key(), AmmError::InvalidMint);
+        require_keys_eq!(user_out.mint, mint_out.key(), AmmError::InvalidMint);
+
+        let sol_fee_collector = &accounts[9];
+        require_keys_eq!(sol_fee_collector.key(), amm.sol_fee_collector, AmmError::InvalidFeeCollector);
+
+        // Accrue the TWAP accumulators on the pre-trade reserves
+        let (vault_a_amount, vault_b_amount) = if swap_a {
+            (vault_in.amount, vault_out.amount)
+        } else {
+            (vault_out.amount, vault_in.amount)
+        };
+        let mut observations: Account<'info, Observations> = Account::try_from(&accounts[12])?;
+        require_keys_eq!(observations.pool, pool_key, AmmError::InvalidPool);
+        update_oracle(&mut pool, &mut observations, vault_a_amount, vault_b_amount)?;
+
+        // Quote against reserves net of uncollected protocol fees
+        let (reserve_a, reserve_b) = pool.reserves(vault_a_amount, vault_b_amount);
+        let (reserve_in, reserve_out) = if swap_a {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        require!(reserve_in > 0 && reserve_out > 0, AmmError::InsufficientLiquidity);
+        let (weight_in, weight_out) = pool.weights_in_out(swap_a);
+
+        // Transfer fees on either mint come off before the curve and before the next hop
+        let pool_input_amount = input_amount
+            .checked_sub(get_transfer_fee(&accounts[3], input_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let pool_output_amount = calculate_swap_output(
+            &pool,
+            Clock::get()?.unix_timestamp,
+            pool_input_amount,
+            SwapSide { reserve: reserve_in, decimals: mint_in.decimals, weight: weight_in },
+            SwapSide { reserve: reserve_out, decimals: mint_out.decimals, weight: weight_out },
+        ).ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let output_amount = pool_output_amount
+            .checked_sub(get_transfer_fee(&accounts[4], pool_output_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        require!(output_amount > 0, AmmError::InvalidAmount);
+        msg!("Hop {}: {} in, {} out", hop, input_amount, output_amount);
+
+        // Fees, transfers and the event, as in a single swap
+        let (trade_fee, protocol_fee) = calculate_swap_fees(&amm, &pool, pool_input_amount)?;
+        settle_pool_swap(
+            SettleAccounts {
+                amm: &amm,
+                pool: &mut pool,
+                pool_authority: pool_authority.clone(),
+                user: self.user.to_account_info(),
+                sol_fee_collector: sol_fee_collector.clone(),
+                system_program: self.system_program.to_account_info(),
+                input: SettleSide {
+                    mint: &mint_in,
+                    vault: &mut vault_in,
+                    user_account: accounts[7].clone(),
+                    token_program: token_program_in.clone(),
+                },
+                output: SettleSide {
+                    mint: &mint_out,
+                    vault: &mut vault_out,
+                    user_account: accounts[8].clone(),
+                    token_program: token_program_out.clone(),
+                },
+            },
+            hook_accounts,
+            swap_a,
+            SwapAmounts {
+                input_amount,
+                pool_input_amount,
+                pool_output_amount,
+                output_amount,
+                trade_fee,
+                protocol_fee,
+            },
+        )?;
+
+        // These accounts were loaded by hand, so Anchor won't write them back; a later hop
+        // through the same pool reads what is persisted here
+        pool.exit(&crate::ID)?;
+        observations.exit(&crate::ID)?;
+
+        // What landed in the user's account is what the next hop spends
+        Ok(output_amount)
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::{
+        utils::{calculate_swap_input, get_transfer_inverse_fee},
+        Swap,
+        SwapAmounts,
+    },
+    errors::*,
+    state::CurveType,
+};
+
+// Exact-output swaps reuse the `Swap` accounts, fee handling and hook-aware transfers;
+// only the quoting runs the other way round.
+impl<'info> Swap<'info> {
+    pub fn swap_tokens_for_exact_tokens(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        swap_a: bool,
+        output_amount: u64,
+        max_input_amount: u64,
+    ) -> Result<()> {
+        msg!("Swapping for exactly {} tokens", output_amount);
+        msg!("Swap direction: {}", if swap_a { "A to B" } else { "B to A" });
+        require!(output_amount > 0, AmmError::InvalidAmount);
+        self.update_oracle()?;
+
+        // The vault has to send enough to cover the output mint's transfer fee
+        let (mint_in, mint_out) = self.mints_in_out(swap_a);
+        let pool_output_amount = output_amount
+            .checked_add(get_transfer_inverse_fee(&mint_out, output_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+        // Calculate the input needed for the requested output, rounded up in favour of the pool
+        let (pool_input_amount, trade_fee, protocol_fee) = if self.pool.curve_type == CurveType::Concentrated {
+            let swap = self.swap_concentrated(remaining_accounts, swap_a, pool_output_amount, false)?;
+            (swap.amount_in, swap.trade_fee, swap.protocol_fee)
+        } else {
+            let pool_input_amount = self.calculate_input_amount(swap_a, pool_output_amount)?;
+            let (trade_fee, protocol_fee) = self.calculate_fees(pool_input_amount)?;
+            (pool_input_amount, trade_fee, protocol_fee)
+        };
+
+        // The user has to send enough to cover the input mint's transfer fee
+        let input_amount = pool_input_amount
+            .checked_add(get_transfer_inverse_fee(&mint_in, pool_input_amount)?)
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+        // Check slippage against what actually leaves the user's account
+        require!(input_amount <= max_input_amount, AmmError::SlippageExceeded);
+
+        self.settle_swap(remaining_accounts, swap_a, SwapAmounts {
+            input_amount,
+            pool_input_amount,
+            pool_output_amount,
+            output_amount,
+            trade_fee,
+            protocol_fee,
+        })
+    }
+
+    fn calculate_input_amount(&self, swap_a: bool, output_amount: u64) -> Result<u64> {
+        let (input, output) = self.swap_sides(swap_a)?;
+
+        // The pool can never pay out its whole output reserve
+        require!(output_amount < output.reserve, AmmError::InsufficientLiquidity);
+
+        calculate_swap_input(
+            &self.pool,
+            Clock::get()?.unix_timestamp,
+            output_amount,
+            input,
+            output,
+        ).ok_or_else(|| error!(AmmError::InvalidAmount))
+    }
+}
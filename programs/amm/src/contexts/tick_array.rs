@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{MAX_TICK, MIN_TICK, TICK_ARRAY_SEED, TICK_ARRAY_SIZE},
+    errors::AmmError,
+    events::TickArrayInitialized,
+    state::{CurveType, Pool, Tick, TickArray},
+};
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArray<'info> {
+    /// The account paying for the tick array's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = pool.curve_type == CurveType::Concentrated @ AmmError::InvalidCurve)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TickArray::LEN,
+        seeds = [TICK_ARRAY_SEED, pool.key().as_ref(), &start_tick_index.to_le_bytes()],
+        bump,
+    )]
+    pub tick_array: Box<Account<'info, TickArray>>,
+
+    /// Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeTickArray<'info> {
+    pub fn initialize_tick_array(&mut self, start_tick_index: i32) -> Result<()> {
+        // Arrays tile the tick range, so the start has to sit on an array boundary
+        require!(
+            start_tick_index == TickArray::start_tick_index(start_tick_index, self.pool.tick_spacing),
+            AmmError::InvalidTickArray
+        );
+        let ticks_per_array = self.pool.tick_spacing as i32 * TICK_ARRAY_SIZE as i32;
+        require!(
+            start_tick_index <= MAX_TICK && start_tick_index + ticks_per_array > MIN_TICK,
+            AmmError::InvalidTickArray
+        );
+
+        self.tick_array.pool = self.pool.key();
+        self.tick_array.start_tick_index = start_tick_index;
+        msg!("Tick array initialized from tick {}", start_tick_index);
+        emit!(TickArrayInitialized {
+            pool: self.pool.key(),
+            tick_array: self.tick_array.key(),
+            start_tick_index,
+        });
+        Ok(())
+    }
+}
+
+/// Tick arrays of a concentrated pool, found by address among the instruction's
+/// remaining accounts and loaded on first use. An array that was never initialized
+/// may be passed as its empty PDA and reads as having no initialized ticks.
+pub(crate) struct TickArrays<'a, 'info> {
+    pool: Pubkey,
+    tick_spacing: u16,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    loaded: Vec<LoadedTickArray<'a, 'info>>,
+}
+
+struct LoadedTickArray<'a, 'info> {
+    start_tick_index: i32,
+    info: &'a AccountInfo<'info>,
+    /// None while the array is uninitialized
+    array: Option<Box<TickArray>>,
+    /// Whether a tick was handed out for writing
+    dirty: bool,
+}
+
+impl<'a, 'info> TickArrays<'a, 'info> {
+    pub fn new(pool: Pubkey, tick_spacing: u16, remaining_accounts: &'a [AccountInfo<'info>]) -> Self {
+        Self {
+            pool,
+            tick_spacing,
+            remaining_accounts,
+            loaded: Vec::new(),
+        }
+    }
+
+    /// Index into `loaded` of the array starting at `start_tick_index`
+    fn load(&mut self, start_tick_index: i32) -> Result<usize> {
+        if let Some(index) = self
+            .loaded
+            .iter()
+            .position(|loaded| loaded.start_tick_index == start_tick_index)
+        {
+            return Ok(index);
+        }
+
+        let (address, _) = TickArray::find_address(&self.pool, start_tick_index);
+        let info = self
+            .remaining_accounts
+            .iter()
+            .find(|info| info.key == &address)
+            .ok_or_else(|| error!(AmmError::TickArrayMissing))?;
+
+        let array = if info.owner == &crate::ID {
+            let array = TickArray::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require_keys_eq!(array.pool, self.pool, AmmError::InvalidTickArray);
+            Some(Box::new(array))
+        } else {
+            require!(info.data_is_empty(), AmmError::InvalidTickArray);
+            None
+        };
+        self.loaded.push(LoadedTickArray {
+            start_tick_index,
+            info,
+            array,
+            dirty: false,
+        });
+        Ok(self.loaded.len() - 1)
+    }
+
+    fn offset(&self, tick: i32) -> usize {
+        let start = TickArray::start_tick_index(tick, self.tick_spacing);
+        ((tick - start) / self.tick_spacing as i32) as usize
+    }
+
+    /// The tick, or None when its array was never initialized
+    pub fn tick(&mut self, tick: i32) -> Result<Option<Tick>> {
+        let index = self.load(TickArray::start_tick_index(tick, self.tick_spacing))?;
+        let offset = self.offset(tick);
+        Ok(self.loaded[index].array.as_ref().map(|array| array.ticks[offset]))
+    }
+
+    /// The tick for writing; its array has to be initialized
+    pub fn tick_mut(&mut self, tick: i32) -> Result<&mut Tick> {
+        let index = self.load(TickArray::start_tick_index(tick, self.tick_spacing))?;
+        let offset = self.offset(tick);
+        let loaded = &mut self.loaded[index];
+        let array = loaded
+            .array
+            .as_mut()
+            .ok_or_else(|| error!(AmmError::TickArrayMissing))?;
+        loaded.dirty = true;
+        Ok(&mut array.ticks[offset])
+    }
+
+    /// Next initialized tick from `tick_current` in the swap direction, searching no
+    /// further than the end of the current tick array. Returns (tick, initialized),
+    /// where an uninitialized result is the array boundary. Going up, that boundary is
+    /// the first tick of the next array and is not read, so the caller has to check it
+    /// if the price actually reaches it.
+    pub fn next_initialized_tick(&mut self, tick_current: i32, a_to_b: bool) -> Result<(i32, bool)> {
+        let spacing = self.tick_spacing as i32;
+        let start = TickArray::start_tick_index(tick_current, self.tick_spacing);
+        let aligned = tick_current.div_euclid(spacing) * spacing;
+
+        if a_to_b {
+            // Ticks at or below the current one, down to the array start
+            let end = start.max(MIN_TICK);
+            let mut tick = aligned;
+            while tick >= end {
+                if self.tick(tick)?.is_some_and(|tick| tick.liquidity_gross > 0) {
+                    return Ok((tick, true));
+                }
+                tick -= spacing;
+            }
+            Ok((end, false))
+        } else {
+            // Ticks above the current one, up to but excluding the start of the next array
+            let end = (start + spacing * TICK_ARRAY_SIZE as i32).min(MAX_TICK);
+            let mut tick = aligned + spacing;
+            while tick < end {
+                if self.tick(tick)?.is_some_and(|tick| tick.liquidity_gross > 0) {
+                    return Ok((tick, true));
+                }
+                tick += spacing;
+            }
+            Ok((end, false))
+        }
+    }
+
+    /// Writes every array a tick was changed in back to its account
+    pub fn exit(&self) -> Result<()> {
+        for loaded in self.loaded.iter().filter(|loaded| loaded.dirty) {
+            if let Some(array) = &loaded.array {
+                require!(loaded.info.is_writable, AmmError::InvalidTickArray);
+                array.try_serialize(&mut &mut loaded.info.try_borrow_mut_data()?[..])?;
+            }
+        }
+        Ok(())
+    }
+}
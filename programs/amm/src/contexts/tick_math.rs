@@ -0,0 +1,371 @@
+use crate::{
+    constants::{FEE_BPS_DENOMINATOR, MAX_TICK, MIN_TICK},
+    contexts::utils::{mul_div, mul_div_ceil},
+};
+
+/// 1.0 in Q64.64
+pub const Q64: u128 = 1 << 64;
+/// Largest liquidity a position, tick or pool can hold, so `liquidity * Q64` fits in a u128
+pub const MAX_LIQUIDITY: u128 = u64::MAX as u128;
+/// `sqrt_price_at_tick(MIN_TICK)`
+pub const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+/// `sqrt_price_at_tick(MAX_TICK)`
+pub const MAX_SQRT_PRICE_X64: u128 = 79_226_673_521_066_979_257_578_248_091;
+
+/// floor(2^64 / sqrt(1.0001)^(2^i)) for bit i of |tick|
+const SQRT_PRICE_FACTORS: [u128; 19] = [
+    0xfffcb933bd6fad37,
+    0xfff97272373d4132,
+    0xfff2e50f5f656932,
+    0xffe5caca7e10e4e6,
+    0xffcb9843d60f6159,
+    0xff973b41fa98c081,
+    0xff2ea16466c96a38,
+    0xfe5dee046a99a2a8,
+    0xfcbe86c7900a88ae,
+    0xf987a7253ac41317,
+    0xf3392b0822b70005,
+    0xe7159475a2c29b74,
+    0xd097f3bdfd2022b8,
+    0xa9f746462d870fdf,
+    0x70d869a156d2a1b8,
+    0x31be135f97d08fd9,
+    0x9aa508b5b7a84e1,
+    0x5d6af8dedb8119,
+    0x2216e584f5fa,
+];
+
+/// sqrt(1.0001^tick) as Q64.64. None outside [MIN_TICK, MAX_TICK].
+pub fn sqrt_price_at_tick(tick: i32) -> Option<u128> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return None;
+    }
+
+    // 1 / sqrt(1.0001)^|tick| as the product of one factor per set bit; each partial
+    // product stays at or below 2^64, so the multiplications cannot overflow
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = Q64;
+    for (bit, factor) in SQRT_PRICE_FACTORS.iter().enumerate() {
+        if abs_tick & (1 << bit) != 0 {
+            ratio = (ratio * factor) >> 64;
+        }
+    }
+
+    // Positive ticks take the reciprocal
+    Some(if tick > 0 { u128::MAX / ratio } else { ratio })
+}
+
+/// Largest tick whose sqrt price is at or below `sqrt_price_x64`
+pub fn tick_at_sqrt_price(sqrt_price_x64: u128) -> Option<i32> {
+    if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64) {
+        return None;
+    }
+
+    // Binary search on the monotonic `sqrt_price_at_tick`
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(mid)? <= sqrt_price_x64 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Some(low)
+}
+
+/// Token A held by `liquidity` between two sqrt prices: L * (upper - lower) / (upper * lower).
+/// Liquidity has to stay below 2^64 for the Q64.64 numerator to fit, which
+/// `add_liquidity_delta` enforces.
+pub fn amount_a_delta(sqrt_price_0: u128, sqrt_price_1: u128, liquidity: u128, round_up: bool) -> Option<u128> {
+    let (lower, upper) = (sqrt_price_0.min(sqrt_price_1), sqrt_price_0.max(sqrt_price_1));
+    if lower == 0 {
+        return None;
+    }
+
+    // floor(floor(x / a) / b) == floor(x / (a * b)), and likewise for ceil
+    let numerator = liquidity.checked_mul(Q64)?;
+    if round_up {
+        let quotient = mul_div_ceil(numerator, upper - lower, upper)?;
+        Some(quotient.div_ceil(lower))
+    } else {
+        Some(mul_div(numerator, upper - lower, upper)? / lower)
+    }
+}
+
+/// Token B held by `liquidity` between two sqrt prices: L * (upper - lower)
+pub fn amount_b_delta(sqrt_price_0: u128, sqrt_price_1: u128, liquidity: u128, round_up: bool) -> Option<u128> {
+    let (lower, upper) = (sqrt_price_0.min(sqrt_price_1), sqrt_price_0.max(sqrt_price_1));
+    if round_up {
+        mul_div_ceil(liquidity, upper - lower, Q64)
+    } else {
+        mul_div(liquidity, upper - lower, Q64)
+    }
+}
+
+/// Sqrt price after adding (or removing) `amount` of A, rounded up so the pool never
+/// gives away more than the amount pays for
+fn next_sqrt_price_from_amount_a(sqrt_price: u128, liquidity: u128, amount: u128, add: bool) -> Option<u128> {
+    if amount == 0 {
+        return Some(sqrt_price);
+    }
+
+    // L * sqrt_price / (L +- amount * sqrt_price) in Q64.64
+    let numerator = liquidity.checked_mul(Q64)?;
+    let product = amount.checked_mul(sqrt_price);
+    if add {
+        match product.and_then(|product| numerator.checked_add(product)) {
+            Some(denominator) => mul_div_ceil(numerator, sqrt_price, denominator),
+            // Same quotient written as L / (L / sqrt_price + amount), still rounded up
+            None => Some(numerator.div_ceil((numerator / sqrt_price).checked_add(amount)?)),
+        }
+    } else {
+        let denominator = numerator.checked_sub(product?)?;
+        if denominator == 0 {
+            return None;
+        }
+        mul_div_ceil(numerator, sqrt_price, denominator)
+    }
+}
+
+/// Sqrt price after adding (or removing) `amount` of B, rounded down for the same reason
+fn next_sqrt_price_from_amount_b(sqrt_price: u128, liquidity: u128, amount: u128, add: bool) -> Option<u128> {
+    // sqrt_price +- amount / L
+    if add {
+        sqrt_price.checked_add(mul_div(amount, Q64, liquidity)?)
+    } else {
+        sqrt_price.checked_sub(mul_div_ceil(amount, Q64, liquidity)?)
+    }
+}
+
+/// One step of a concentrated swap, within a range of constant liquidity
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SwapStep {
+    /// Sqrt price once the step is done
+    pub sqrt_price_next: u128,
+    /// Input taken by the curve, excluding the fee
+    pub amount_in: u64,
+    /// Output paid by the pool
+    pub amount_out: u64,
+    /// LP trading fee taken on top of `amount_in`
+    pub fee_amount: u64,
+}
+
+/// Moves the price from `sqrt_price_current` towards `sqrt_price_target` until either
+/// the target is reached or `amount_remaining` (input when `exact_input`, otherwise
+/// output) is used up. A target below the current price swaps A to B.
+pub fn compute_swap_step(
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    trade_fee_bps: u16,
+    exact_input: bool,
+) -> Option<SwapStep> {
+    let a_to_b = sqrt_price_current >= sqrt_price_target;
+    let fee_denominator = FEE_BPS_DENOMINATOR as u128;
+    let net_share = fee_denominator.checked_sub(trade_fee_bps as u128)?;
+    if net_share == 0 {
+        return None;
+    }
+    let amount_remaining = amount_remaining as u128;
+
+    let sqrt_price_next = if exact_input {
+        let amount_remaining_less_fee = amount_remaining * net_share / fee_denominator;
+        let amount_in_to_target = if a_to_b {
+            amount_a_delta(sqrt_price_target, sqrt_price_current, liquidity, true)?
+        } else {
+            amount_b_delta(sqrt_price_current, sqrt_price_target, liquidity, true)?
+        };
+        if amount_remaining_less_fee >= amount_in_to_target {
+            sqrt_price_target
+        } else if a_to_b {
+            next_sqrt_price_from_amount_a(sqrt_price_current, liquidity, amount_remaining_less_fee, true)?
+        } else {
+            next_sqrt_price_from_amount_b(sqrt_price_current, liquidity, amount_remaining_less_fee, true)?
+        }
+    } else {
+        let amount_out_to_target = if a_to_b {
+            amount_b_delta(sqrt_price_target, sqrt_price_current, liquidity, false)?
+        } else {
+            amount_a_delta(sqrt_price_current, sqrt_price_target, liquidity, false)?
+        };
+        if amount_remaining >= amount_out_to_target {
+            sqrt_price_target
+        } else if a_to_b {
+            next_sqrt_price_from_amount_b(sqrt_price_current, liquidity, amount_remaining, false)?
+        } else {
+            next_sqrt_price_from_amount_a(sqrt_price_current, liquidity, amount_remaining, false)?
+        }
+    };
+
+    // Input rounds up and output rounds down, both in favour of the pool
+    let (amount_in, mut amount_out) = if a_to_b {
+        (
+            amount_a_delta(sqrt_price_next, sqrt_price_current, liquidity, true)?,
+            amount_b_delta(sqrt_price_next, sqrt_price_current, liquidity, false)?,
+        )
+    } else {
+        (
+            amount_b_delta(sqrt_price_current, sqrt_price_next, liquidity, true)?,
+            amount_a_delta(sqrt_price_current, sqrt_price_next, liquidity, false)?,
+        )
+    };
+    if !exact_input {
+        amount_out = amount_out.min(amount_remaining);
+    }
+
+    // A step that stops short of its target uses up the whole input, and whatever the
+    // curve did not take is the fee
+    let fee_amount = if exact_input && sqrt_price_next != sqrt_price_target {
+        amount_remaining.checked_sub(amount_in)?
+    } else {
+        mul_div_ceil(amount_in, trade_fee_bps as u128, net_share)?
+    };
+
+    Some(SwapStep {
+        sqrt_price_next,
+        amount_in: u64::try_from(amount_in).ok()?,
+        amount_out: u64::try_from(amount_out).ok()?,
+        fee_amount: u64::try_from(fee_amount).ok()?,
+    })
+}
+
+/// Liquidity after applying a signed change, or None if it would leave [0, `MAX_LIQUIDITY`]
+pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Option<u128> {
+    let liquidity = if delta < 0 {
+        liquidity.checked_sub(delta.unsigned_abs())?
+    } else {
+        liquidity.checked_add(delta as u128)?
+    };
+    (liquidity <= MAX_LIQUIDITY).then_some(liquidity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqrt_price_at_tick_matches_the_bounds() {
+        assert_eq!(sqrt_price_at_tick(0), Some(Q64));
+        assert_eq!(sqrt_price_at_tick(MIN_TICK), Some(MIN_SQRT_PRICE_X64));
+        assert_eq!(sqrt_price_at_tick(MAX_TICK), Some(MAX_SQRT_PRICE_X64));
+        assert_eq!(sqrt_price_at_tick(MIN_TICK - 1), None);
+        assert_eq!(sqrt_price_at_tick(MAX_TICK + 1), None);
+    }
+
+    #[test]
+    fn sqrt_price_at_tick_is_strictly_increasing() {
+        let ticks = [MIN_TICK, MIN_TICK + 1, -100_000, -2, -1, 0, 1, 2, 100_000, MAX_TICK - 1, MAX_TICK];
+        for pair in ticks.windows(2) {
+            assert!(sqrt_price_at_tick(pair[0]).unwrap() < sqrt_price_at_tick(pair[1]).unwrap(), "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn sqrt_price_at_tick_tracks_one_basis_point_per_tick() {
+        // sqrt(1.0001) * 2^64 and 2^64 / sqrt(1.0001), to within a unit or two of rounding
+        let up = sqrt_price_at_tick(1).unwrap();
+        let down = sqrt_price_at_tick(-1).unwrap();
+        assert!(up.abs_diff(18_447_666_387_855_959_850) <= 2, "{up}");
+        assert!(down.abs_diff(18_445_821_805_675_392_311) <= 2, "{down}");
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_round_trips_at_the_bounds() {
+        assert_eq!(tick_at_sqrt_price(MIN_SQRT_PRICE_X64), Some(MIN_TICK));
+        assert_eq!(tick_at_sqrt_price(MAX_SQRT_PRICE_X64), Some(MAX_TICK));
+        assert_eq!(tick_at_sqrt_price(MIN_SQRT_PRICE_X64 - 1), None);
+        assert_eq!(tick_at_sqrt_price(MAX_SQRT_PRICE_X64 + 1), None);
+        assert_eq!(tick_at_sqrt_price(MAX_SQRT_PRICE_X64 - 1), Some(MAX_TICK - 1));
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_rounds_down_to_the_tick() {
+        for tick in [MIN_TICK + 1, -443_000, -60, -1, 0, 1, 60, 443_000, MAX_TICK - 1] {
+            let sqrt_price = sqrt_price_at_tick(tick).unwrap();
+            assert_eq!(tick_at_sqrt_price(sqrt_price), Some(tick));
+            assert_eq!(tick_at_sqrt_price(sqrt_price - 1), Some(tick - 1));
+            assert_eq!(tick_at_sqrt_price(sqrt_price + 1), Some(tick));
+        }
+    }
+
+    #[test]
+    fn amount_deltas_round_up_by_at_most_one() {
+        let lower = sqrt_price_at_tick(-1_000).unwrap();
+        let upper = sqrt_price_at_tick(2_000).unwrap();
+        for liquidity in [1, 1_000, 123_456_789, MAX_LIQUIDITY] {
+            let a_down = amount_a_delta(lower, upper, liquidity, false).unwrap();
+            let a_up = amount_a_delta(lower, upper, liquidity, true).unwrap();
+            let b_down = amount_b_delta(lower, upper, liquidity, false).unwrap();
+            let b_up = amount_b_delta(lower, upper, liquidity, true).unwrap();
+            assert!(a_up >= a_down && a_up - a_down <= 1);
+            assert!(b_up >= b_down && b_up - b_down <= 1);
+        }
+        // The order of the prices does not matter
+        assert_eq!(amount_a_delta(upper, lower, 1_000, true), amount_a_delta(lower, upper, 1_000, true));
+    }
+
+    #[test]
+    fn amount_deltas_fit_at_max_liquidity_over_the_full_range() {
+        assert!(amount_a_delta(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, MAX_LIQUIDITY, true).is_some());
+        assert!(amount_b_delta(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, MAX_LIQUIDITY, true).is_some());
+        assert_eq!(amount_a_delta(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, MAX_LIQUIDITY + 1, true), None);
+        assert_eq!(amount_a_delta(0, Q64, 1_000, true), None);
+    }
+
+    #[test]
+    fn add_liquidity_delta_stays_within_bounds() {
+        assert_eq!(add_liquidity_delta(10, 5), Some(15));
+        assert_eq!(add_liquidity_delta(10, -10), Some(0));
+        assert_eq!(add_liquidity_delta(10, -11), None);
+        assert_eq!(add_liquidity_delta(MAX_LIQUIDITY - 1, 1), Some(MAX_LIQUIDITY));
+        assert_eq!(add_liquidity_delta(MAX_LIQUIDITY, 1), None);
+        assert_eq!(add_liquidity_delta(0, i128::MAX), None);
+    }
+
+    #[test]
+    fn exact_input_step_stops_short_of_the_target_and_keeps_the_rest_as_fee() {
+        let liquidity = 1_000_000_000_000;
+        for a_to_b in [true, false] {
+            let target = sqrt_price_at_tick(if a_to_b { -10_000 } else { 10_000 }).unwrap();
+            let step = compute_swap_step(Q64, target, liquidity, 1_000_000, 30, true).unwrap();
+
+            assert_ne!(step.sqrt_price_next, target);
+            assert_eq!(step.amount_in + step.fee_amount, 1_000_000);
+            assert!(step.fee_amount >= 1_000_000 * 30 / 10_000);
+            // The price moves in the swap direction, and the output is below the input at price ~1
+            assert_eq!(step.sqrt_price_next < Q64, a_to_b);
+            assert!(step.amount_out < step.amount_in);
+        }
+    }
+
+    #[test]
+    fn exact_input_step_reaches_a_close_target() {
+        let target = sqrt_price_at_tick(-1).unwrap();
+        let step = compute_swap_step(Q64, target, 1_000_000_000, u64::MAX, 30, true).unwrap();
+
+        assert_eq!(step.sqrt_price_next, target);
+        let amount_in = amount_a_delta(target, Q64, 1_000_000_000, true).unwrap() as u64;
+        assert_eq!(step.amount_in, amount_in);
+        assert_eq!(step.fee_amount as u128, mul_div_ceil(amount_in as u128, 30, 9_970).unwrap());
+    }
+
+    #[test]
+    fn exact_output_step_never_pays_out_more_than_asked() {
+        let liquidity = 1_000_000_000_000;
+        for a_to_b in [true, false] {
+            let target = sqrt_price_at_tick(if a_to_b { MIN_TICK } else { MAX_TICK }).unwrap();
+            let step = compute_swap_step(Q64, target, liquidity, 1_000_000, 30, false).unwrap();
+
+            assert!((999_999..=1_000_000).contains(&step.amount_out), "{step:?}");
+            // Input rounds up, so it costs more than the output at price ~1
+            assert!(step.amount_in > step.amount_out, "{step:?}");
+        }
+    }
+
+    #[test]
+    fn swap_step_rejects_a_fee_of_the_whole_input() {
+        assert!(compute_swap_step(Q64, MIN_SQRT_PRICE_X64, 1_000, 1_000, 10_000, true).is_none());
+    }
+}
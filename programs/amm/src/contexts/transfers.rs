@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{transfer_hook, StateWithExtensions},
+    state::Mint as MintState,
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+
+/// Program of the mint's `TransferHook` extension, if it has one. Mints owned by
+/// the legacy token program can never carry the extension.
+pub fn get_transfer_hook_program_id(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    if mint.owner != &spl_token_2022::id() {
+        return Ok(None);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(transfer_hook::get_program_id(&mint_state))
+}
+
+/// `transfer_checked` through the mint's token program. When the mint has a
+/// transfer hook, the hook program, its `ExtraAccountMetaList` and every account
+/// that list resolves to are looked up by key in `additional_accounts` (the
+/// instruction's remaining accounts) and appended to the CPI, so any hook layout
+/// works. Mints without a hook get a plain `transfer_checked`. Pass empty
+/// `signer_seeds` when `authority` signs the transaction itself, or the pool
+/// authority seeds when paying out of a vault.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    let mut account_infos = vec![
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+    ];
+
+    // Resolve the hook's extra accounts from its on-chain ExtraAccountMetaList
+    if let Some(hook_program_id) = get_transfer_hook_program_id(mint)? {
+        add_extra_accounts_for_execute_cpi(
+            &mut transfer_ix,
+            &mut account_infos,
+            &hook_program_id,
+            from.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+            amount,
+            additional_accounts,
+        )?;
+    }
+
+    account_infos.push(token_program.clone());
+    invoke_signed(&transfer_ix, &account_infos, signer_seeds)?;
+    Ok(())
+}
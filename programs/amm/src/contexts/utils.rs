@@ -1,19 +1,446 @@
 // Utility functions for AMM calculations
+//
+// These are used by the on-chain instructions, so off-chain quotes that call
+// them get exactly the same results as the program.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+
+use crate::{
+    constants::{
+        FEE_BPS_DENOMINATOR,
+        MAX_WEIGHTED_IN_RATIO_BPS,
+        MAX_WEIGHTED_OUT_RATIO_BPS,
+        STABLE_SWAP_MAX_ITERATIONS,
+    },
+    errors::AmmError,
+    state::{CurveType, Pool},
+};
+
+pub fn check_deadline(deadline: Option<i64>) -> Result<()> {
+    // No deadline means the caller accepts whenever the transaction lands
+    if let Some(deadline) = deadline {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= deadline, AmmError::DeadlineExceeded);
+    }
+    Ok(())
+}
+
+/// Fee Token-2022 withholds from `amount` when it is transferred this epoch.
+/// Zero for mints without the transfer fee extension.
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+
+    transfer_fee_config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))
+}
+
+/// Fee to add on top of `net_amount` so that exactly `net_amount` arrives after the
+/// transfer this epoch. Zero for mints without the transfer fee extension.
+pub fn get_transfer_inverse_fee(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+
+    transfer_fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or_else(|| error!(AmmError::InvalidAmount))
+}
+
+pub fn calculate_trade_fee(input_amount: u64, trade_fee_bps: u16) -> Option<u64> {
+    // fee = ceil(dx * bps / 10_000), rounded up so the LPs never lose the remainder
+    let numerator = (input_amount as u128).checked_mul(trade_fee_bps as u128)?;
+    let denominator = FEE_BPS_DENOMINATOR as u128;
+    u64::try_from(numerator.checked_add(denominator - 1)? / denominator).ok()
+}
+
+pub fn calculate_protocol_fee(trade_fee: u64, protocol_fee_share_bps: u16) -> Option<u64> {
+    // protocol cut = floor(fee * share / 10_000), the remainder stays with the LPs
+    let numerator = (trade_fee as u128).checked_mul(protocol_fee_share_bps as u128)?;
+    u64::try_from(numerator / FEE_BPS_DENOMINATOR as u128).ok()
+}
 
 pub fn calculate_constant_product_swap_output(
     input_amount: u64,
     input_reserve: u64,
     output_reserve: u64,
-) -> u64 {
+    trade_fee_bps: u16,
+) -> Option<u64> {
     // Constant product formula: (x + dx) * (y - dy) = x * y
-    // where dx is input_amount and dy is output_amount
+    // where dx is input_amount net of the LP trading fee and dy is output_amount
     // Solving for dy: dy = (dx * y) / (x + dx)
+    // The fee stays in the input vault, so it accrues to the LPs.
     
     if input_reserve == 0 || output_reserve == 0 {
-        return 0;
+        return None;
     }
     
-    (input_amount * output_reserve) / (input_reserve + input_amount)
+    let trade_fee = calculate_trade_fee(input_amount, trade_fee_bps)?;
+    let input = input_amount.checked_sub(trade_fee)? as u128;
+
+    let numerator = input.checked_mul(output_reserve as u128)?;
+    let denominator = (input_reserve as u128).checked_add(input)?;
+    u64::try_from(numerator / denominator).ok()
+}
+
+pub fn calculate_constant_product_swap_input(
+    output_amount: u64,
+    input_reserve: u64,
+    output_reserve: u64,
+    trade_fee_bps: u16,
+) -> Option<u64> {
+    // Inverse of calculate_constant_product_swap_output, rounded up at every step so that
+    // feeding the result back through it always yields at least output_amount.
+    // Net input: dx = ceil(x * dy / (y - dy))
+    // Gross input: ceil(dx * 10_000 / (10_000 - bps))
+
+    if input_reserve == 0 || output_reserve == 0 || output_amount >= output_reserve {
+        return None;
+    }
+
+    let numerator = (input_reserve as u128).checked_mul(output_amount as u128)?;
+    let denominator = (output_reserve - output_amount) as u128;
+    let input = numerator.checked_add(denominator - 1)? / denominator;
+
+    let fee_denominator = FEE_BPS_DENOMINATOR as u128;
+    let net_share = fee_denominator.checked_sub(trade_fee_bps as u128)?;
+    if net_share == 0 {
+        return None;
+    }
+    let gross = input.checked_mul(fee_denominator)?.checked_add(net_share - 1)? / net_share;
+    u64::try_from(gross).ok()
+}
+
+/// One side of a swap as the curves see it
+#[derive(Clone, Copy, Debug)]
+pub struct SwapSide {
+    /// Pool balance excluding uncollected protocol fees
+    pub reserve: u64,
+    /// Decimals of the side's mint
+    pub decimals: u8,
+    /// Weight of the side, only read by weighted pools
+    pub weight: u16,
+}
+
+/// Swap output for `pool`'s curve at time `now`
+pub fn calculate_swap_output(
+    pool: &Pool,
+    now: i64,
+    input_amount: u64,
+    input: SwapSide,
+    output: SwapSide,
+) -> Option<u64> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => calculate_constant_product_swap_output(
+            input_amount,
+            input.reserve,
+            output.reserve,
+            pool.trade_fee_bps,
+        ),
+        CurveType::StableSwap => calculate_stable_swap_output(
+            input_amount,
+            input.reserve,
+            output.reserve,
+            precision_multipliers(input.decimals, output.decimals)?,
+            pool.amplification(now),
+            pool.trade_fee_bps,
+        ),
+        CurveType::Weighted => calculate_weighted_swap_output(
+            input_amount,
+            input,
+            output,
+            pool.trade_fee_bps,
+        ),
+        // Concentrated pools are priced off their ticks, not the vault reserves
+        CurveType::Concentrated => None,
+    }
+}
+
+/// Swap input needed for `output_amount` on `pool`'s curve at time `now`
+pub fn calculate_swap_input(
+    pool: &Pool,
+    now: i64,
+    output_amount: u64,
+    input: SwapSide,
+    output: SwapSide,
+) -> Option<u64> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => calculate_constant_product_swap_input(
+            output_amount,
+            input.reserve,
+            output.reserve,
+            pool.trade_fee_bps,
+        ),
+        CurveType::StableSwap => calculate_stable_swap_input(
+            output_amount,
+            input.reserve,
+            output.reserve,
+            precision_multipliers(input.decimals, output.decimals)?,
+            pool.amplification(now),
+            pool.trade_fee_bps,
+        ),
+        CurveType::Weighted => calculate_weighted_swap_input(
+            output_amount,
+            input,
+            output,
+            pool.trade_fee_bps,
+        ),
+        // Concentrated pools are priced off their ticks, not the vault reserves
+        CurveType::Concentrated => None,
+    }
+}
+
+/// Factors that scale both sides of a pair up to the larger of their decimals,
+/// so the StableSwap invariant compares like with like
+pub fn precision_multipliers(decimals_x: u8, decimals_y: u8) -> Option<(u128, u128)> {
+    let decimals = decimals_x.max(decimals_y);
+    Some((
+        10u128.checked_pow((decimals - decimals_x) as u32)?,
+        10u128.checked_pow((decimals - decimals_y) as u32)?,
+    ))
+}
+
+pub fn calculate_stable_swap_output(
+    input_amount: u64,
+    input_reserve: u64,
+    output_reserve: u64,
+    (input_multiplier, output_multiplier): (u128, u128),
+    amp: u64,
+    trade_fee_bps: u16,
+) -> Option<u64> {
+    // Keep D fixed, move x by the input net of the LP trading fee and solve for the new y.
+    // dy = y - y' - 1, the extra unit rounds in favour of the pool.
+
+    if input_reserve == 0 || output_reserve == 0 {
+        return None;
+    }
+
+    let trade_fee = calculate_trade_fee(input_amount, trade_fee_bps)?;
+    let input = (input_amount.checked_sub(trade_fee)? as u128).checked_mul(input_multiplier)?;
+
+    let x = (input_reserve as u128).checked_mul(input_multiplier)?;
+    let y = (output_reserve as u128).checked_mul(output_multiplier)?;
+    let d = calculate_stable_swap_d(x, y, amp)?;
+    let new_y = calculate_stable_swap_y(x.checked_add(input)?, d, amp)?;
+
+    let output = y.checked_sub(new_y)?.saturating_sub(1) / output_multiplier;
+    u64::try_from(output).ok()
+}
+
+pub fn calculate_stable_swap_input(
+    output_amount: u64,
+    input_reserve: u64,
+    output_reserve: u64,
+    (input_multiplier, output_multiplier): (u128, u128),
+    amp: u64,
+    trade_fee_bps: u16,
+) -> Option<u64> {
+    // Inverse of calculate_stable_swap_output: solve for the x that leaves y - dy - 1,
+    // then gross up for the trade fee, rounding up at every step.
+
+    if input_reserve == 0 || output_reserve == 0 || output_amount >= output_reserve {
+        return None;
+    }
+
+    let x = (input_reserve as u128).checked_mul(input_multiplier)?;
+    let y = (output_reserve as u128).checked_mul(output_multiplier)?;
+    let d = calculate_stable_swap_d(x, y, amp)?;
+
+    let output = (output_amount as u128).checked_mul(output_multiplier)?.checked_add(1)?;
+    let new_x = calculate_stable_swap_y(y.checked_sub(output)?, d, amp)?;
+    let input = new_x.checked_sub(x)?.checked_add(1)?;
+    let input = input.checked_add(input_multiplier - 1)? / input_multiplier;
+
+    let fee_denominator = FEE_BPS_DENOMINATOR as u128;
+    let net_share = fee_denominator.checked_sub(trade_fee_bps as u128)?;
+    if net_share == 0 {
+        return None;
+    }
+    let gross = input.checked_mul(fee_denominator)?.checked_add(net_share - 1)? / net_share;
+    u64::try_from(gross).ok()
+}
+
+/// StableSwap invariant D for normalized balances `x` and `y`, from
+/// A·n^n·(x + y) + D = A·n^n·D + D^(n+1) / (n^n·x·y) with n = 2,
+/// by Newton's method: D' = (Ann·S + n·D_P)·D / ((Ann − 1)·D + (n + 1)·D_P)
+/// where D_P = D^3 / (4xy)
+pub fn calculate_stable_swap_d(x: u128, y: u128, amp: u64) -> Option<u128> {
+    let sum = x.checked_add(y)?;
+    if sum == 0 {
+        return Some(0);
+    }
+    if x == 0 || y == 0 || amp == 0 {
+        return None;
+    }
+
+    let ann = (amp as u128).checked_mul(4)?;
+    let mut d = sum;
+    for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+        let d_p = mul_div(mul_div(d, d, x.checked_mul(2)?)?, d, y.checked_mul(2)?)?;
+        let previous_d = d;
+
+        let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(2)?)?;
+        let denominator = (ann - 1).checked_mul(d)?.checked_add(d_p.checked_mul(3)?)?;
+        d = mul_div(numerator, d, denominator)?;
+
+        if d.abs_diff(previous_d) <= 1 {
+            return Some(d);
+        }
+    }
+
+    // Not converging means the balances are too far out of range to price
+    None
+}
+
+/// Balance of the other side that keeps invariant `d` when one side holds `x`, by
+/// Newton's method on y^2 + (b − D)·y = c: y' = (y^2 + c) / (2y + b − D)
+/// where b = x + D / Ann and c = D^3 / (4·x·Ann). Rounded up, in the pool's favour.
+pub fn calculate_stable_swap_y(x: u128, d: u128, amp: u64) -> Option<u128> {
+    if x == 0 || amp == 0 {
+        return None;
+    }
+
+    // y^2 and c outgrow a u128 long before the balances do, so each is only ever
+    // formed divided by the Newton denominator
+    let ann = (amp as u128).checked_mul(4)?;
+    let d_squared_over_2x = mul_div_ceil(d, d, x.checked_mul(2)?)?;
+    let b = x.checked_add(d / ann)?;
+
+    let mut y = d;
+    for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+        let previous_y = y;
+
+        let denominator = y.checked_mul(2)?.checked_add(b)?.checked_sub(d)?;
+        let c_over_denominator = mul_div_ceil(d_squared_over_2x, d, denominator)?.div_ceil(ann.checked_mul(2)?);
+        y = mul_div_ceil(y, y, denominator)?.checked_add(c_over_denominator)?;
+
+        if y.abs_diff(previous_y) <= 1 {
+            return Some(y);
+        }
+    }
+
+    None
+}
+
+/// First StableSwap deposit: LP tokens equal to D of the normalized amounts, which
+/// is in units of the larger decimals, the same decimals the LP mint uses
+pub fn calculate_stable_swap_initial_lp(
+    amount_a: u64,
+    amount_b: u64,
+    decimals_a: u8,
+    decimals_b: u8,
+    amp: u64,
+) -> Option<u64> {
+    let (multiplier_a, multiplier_b) = precision_multipliers(decimals_a, decimals_b)?;
+    let d = calculate_stable_swap_d(
+        (amount_a as u128).checked_mul(multiplier_a)?,
+        (amount_b as u128).checked_mul(multiplier_b)?,
+        amp,
+    )?;
+    u64::try_from(d).ok()
+}
+
+pub fn calculate_weighted_swap_output(
+    input_amount: u64,
+    input: SwapSide,
+    output: SwapSide,
+    trade_fee_bps: u16,
+) -> Option<u64> {
+    // Balancer out-given-in, with dx net of the LP trading fee:
+    // dy = y * (1 - (x / (x + dx)) ^ (w_in / w_out))
+    // The base and the power round up, so the output rounds down.
+
+    if input.reserve == 0 || output.reserve == 0 || input.weight == 0 || output.weight == 0 {
+        return None;
+    }
+
+    let trade_fee = calculate_trade_fee(input_amount, trade_fee_bps)?;
+    let input_amount = input_amount.checked_sub(trade_fee)? as u128;
+    let input_reserve = input.reserve as u128;
+    let output_reserve = output.reserve as u128;
+    if input_amount > input_reserve * MAX_WEIGHTED_IN_RATIO_BPS as u128 / FEE_BPS_DENOMINATOR as u128 {
+        return None;
+    }
+
+    let denominator = input_reserve.checked_add(input_amount)?;
+    let base = (input_reserve * WAD as u128).checked_add(denominator - 1)? / denominator;
+    let exponent = input.weight as u128 * WAD as u128 / output.weight as u128;
+    let power = pow_wad_up(base as i128, exponent as i128)?.min(WAD) as u128;
+
+    let output_amount = mul_div(output_reserve, WAD as u128 - power, WAD as u128)?;
+    u64::try_from(output_amount).ok()
+}
+
+pub fn calculate_weighted_swap_input(
+    output_amount: u64,
+    input: SwapSide,
+    output: SwapSide,
+    trade_fee_bps: u16,
+) -> Option<u64> {
+    // Balancer in-given-out: dx = x * ((y / (y - dy)) ^ (w_out / w_in) - 1),
+    // rounded up at every step, then grossed up for the trade fee.
+
+    if input.reserve == 0 || output.reserve == 0 || input.weight == 0 || output.weight == 0 {
+        return None;
+    }
+
+    let output_amount = output_amount as u128;
+    let input_reserve = input.reserve as u128;
+    let output_reserve = output.reserve as u128;
+    if output_amount > output_reserve * MAX_WEIGHTED_OUT_RATIO_BPS as u128 / FEE_BPS_DENOMINATOR as u128 {
+        return None;
+    }
+
+    let denominator = output_reserve.checked_sub(output_amount)?;
+    let base = (output_reserve * WAD as u128).checked_add(denominator - 1)? / denominator;
+    let exponent = (output.weight as u128 * WAD as u128).checked_add(input.weight as u128 - 1)? / input.weight as u128;
+    let power = pow_wad_up(base as i128, exponent as i128)? as u128;
+
+    let input_amount = mul_div(input_reserve, power.checked_sub(WAD as u128)?, WAD as u128)?.checked_add(1)?;
+
+    let fee_denominator = FEE_BPS_DENOMINATOR as u128;
+    let net_share = fee_denominator.checked_sub(trade_fee_bps as u128)?;
+    if net_share == 0 {
+        return None;
+    }
+    let gross = input_amount.checked_mul(fee_denominator)?.checked_add(net_share - 1)? / net_share;
+    u64::try_from(gross).ok()
+}
+
+/// First weighted deposit: LP tokens equal to the weighted geometric mean
+/// a^w_a * b^w_b, which is sqrt(a * b) for a 50/50 pool
+pub fn calculate_weighted_initial_lp(
+    amount_a: u64,
+    amount_b: u64,
+    weight_a: u16,
+    weight_b: u16,
+) -> Option<u64> {
+    if amount_a == 0 || amount_b == 0 {
+        return None;
+    }
+
+    let total_weight = weight_a as i128 + weight_b as i128;
+    if total_weight == 0 {
+        return None;
+    }
+
+    // exp((w_a * ln a + w_b * ln b) / (w_a + w_b)), rounded down
+    let ln_a = ln_wad((amount_a as i128).checked_mul(WAD)?)?;
+    let ln_b = ln_wad((amount_b as i128).checked_mul(WAD)?)?;
+    let exponent = (ln_a * weight_a as i128 + ln_b * weight_b as i128) / total_weight;
+    let mean = exp_wad(exponent)?;
+    let mean = mean.checked_sub(mean / WAD * MAX_POW_RELATIVE_ERROR + 2)?.max(0);
+    u64::try_from(mean / WAD).ok()
 }
 
 pub fn calculate_lp_tokens_to_mint(
@@ -22,16 +449,465 @@ pub fn calculate_lp_tokens_to_mint(
     total_lp_supply: u64,
     reserve_a: u64,
     reserve_b: u64,
-) -> u64 {
+) -> Option<u64> {
     if total_lp_supply == 0 {
-        // First liquidity deposit
-        return (amount_a + amount_b) / 2;
+        // First liquidity deposit: geometric mean of the two amounts
+        let product = (amount_a as u128).checked_mul(amount_b as u128)?;
+        return u64::try_from(integer_sqrt(product)).ok();
     }
     
+    if reserve_a == 0 || reserve_b == 0 {
+        return None;
+    }
+
     // Calculate LP tokens based on the proportion of liquidity being added
-    let lp_from_a = (amount_a * total_lp_supply) / reserve_a;
-    let lp_from_b = (amount_b * total_lp_supply) / reserve_b;
+    let supply = total_lp_supply as u128;
+    let lp_from_a = (amount_a as u128).checked_mul(supply)? / reserve_a as u128;
+    let lp_from_b = (amount_b as u128).checked_mul(supply)? / reserve_b as u128;
     
     // Return the minimum to ensure fair distribution
-    std::cmp::min(lp_from_a, lp_from_b)
-} 
\ No newline at end of file
+    u64::try_from(std::cmp::min(lp_from_a, lp_from_b)).ok()
+}
+
+pub fn calculate_deposit_amounts(
+    max_amount_a: u64,
+    max_amount_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+) -> Option<(u64, u64)> {
+    // Empty pool: the first depositor sets the price
+    if reserve_a == 0 && reserve_b == 0 {
+        return Some((max_amount_a, max_amount_b));
+    }
+
+    if reserve_a == 0 || reserve_b == 0 {
+        return None;
+    }
+
+    // Try to use all of A and take the matching amount of B: b = a * reserve_b / reserve_a
+    let amount_b_optimal = (max_amount_a as u128).checked_mul(reserve_b as u128)? / reserve_a as u128;
+    if amount_b_optimal <= max_amount_b as u128 {
+        return Some((max_amount_a, amount_b_optimal as u64));
+    }
+
+    // Otherwise B is the limiting side: a = b * reserve_a / reserve_b
+    let amount_a_optimal = (max_amount_b as u128).checked_mul(reserve_a as u128)? / reserve_b as u128;
+    if amount_a_optimal <= max_amount_a as u128 {
+        return Some((amount_a_optimal as u64, max_amount_b));
+    }
+
+    None
+}
+
+/// 1.0 in the signed 18-decimal fixed point used by the weighted curve
+const WAD: i128 = 1_000_000_000_000_000_000;
+/// ln(2) in WAD
+const LN_2_WAD: i128 = 693_147_180_559_945_309;
+/// Bound on the relative error of `pow_wad_up`, in WAD (1e-13)
+const MAX_POW_RELATIVE_ERROR: i128 = 100_000;
+/// exp() above this would overflow the WAD range
+const MAX_EXP_WAD: i128 = 46 * WAD;
+/// exp() below this is less than one WAD unit
+const MIN_EXP_WAD: i128 = -42 * WAD;
+
+/// ln(x) for a positive WAD `x`, in WAD
+pub fn ln_wad(x: i128) -> Option<i128> {
+    if x <= 0 {
+        return None;
+    }
+
+    // Write x = m * 2^k with m in [1, 2)
+    let mut m = x;
+    let mut k: i128 = 0;
+    while m >= 2 * WAD {
+        m /= 2;
+        k += 1;
+    }
+    while m < WAD {
+        m *= 2;
+        k -= 1;
+    }
+
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1) < 1/3
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z_squared = z * z / WAD;
+    let mut term = z;
+    let mut sum = 0;
+    let mut n = 1;
+    while term != 0 {
+        sum += term / n;
+        term = term * z_squared / WAD;
+        n += 2;
+    }
+
+    k.checked_mul(LN_2_WAD)?.checked_add(2 * sum)
+}
+
+/// e^x for a WAD `x`, in WAD. None above `MAX_EXP_WAD`.
+pub fn exp_wad(x: i128) -> Option<i128> {
+    if x < MIN_EXP_WAD {
+        return Some(0);
+    }
+    if x > MAX_EXP_WAD {
+        return None;
+    }
+
+    // Write x = k * ln(2) + r with r in [0, ln(2)), so e^x = 2^k * e^r
+    let k = x.div_euclid(LN_2_WAD);
+    let r = x - k * LN_2_WAD;
+
+    // Taylor series of e^r
+    let mut term = WAD;
+    let mut sum = WAD;
+    let mut n = 1;
+    loop {
+        term = term * r / WAD / n;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+        n += 1;
+    }
+
+    if k >= 0 {
+        sum.checked_mul(1i128.checked_shl(k as u32)?)
+    } else {
+        Some(sum.checked_shr((-k) as u32).unwrap_or(0))
+    }
+}
+
+/// base^exponent for WAD values, rounded up past the approximation error so callers
+/// can round in the pool's favour
+pub fn pow_wad_up(base: i128, exponent: i128) -> Option<i128> {
+    let power = exp_wad(ln_wad(base)?.checked_mul(exponent)? / WAD)?;
+    power.checked_add(power / WAD * MAX_POW_RELATIVE_ERROR + 2)
+}
+
+/// floor(a * b / c) with a 256-bit intermediate product. None when `c` is zero or
+/// the quotient does not fit in a u128.
+pub fn mul_div(a: u128, b: u128, c: u128) -> Option<u128> {
+    if c == 0 {
+        return None;
+    }
+
+    // Schoolbook multiply on 64-bit limbs into (hi, lo)
+    const LOW_MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & LOW_MASK);
+    let (b_hi, b_lo) = (b >> 64, b & LOW_MASK);
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+    let middle = (lo_lo >> 64) + (hi_lo & LOW_MASK) + (lo_hi & LOW_MASK);
+    let lo = (lo_lo & LOW_MASK) | (middle << 64);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+    if hi >= c {
+        return None;
+    }
+
+    // Restoring long division; the remainder always stays below c
+    let mut remainder = hi;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+/// ceil(a * b / c), see `mul_div`
+pub fn mul_div_ceil(a: u128, b: u128, c: u128) -> Option<u128> {
+    let quotient = mul_div(a, b, c)?;
+
+    // The remainder is below c < 2^128, so it is zero exactly when the low words match
+    if a.wrapping_mul(b) == quotient.wrapping_mul(c) {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1)
+    }
+}
+
+pub fn integer_sqrt(value: u128) -> u128 {
+    // Babylonian method, rounds down
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = (x >> 1) + (x & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{MAX_AMP, MIN_AMP};
+
+    /// Deterministic pseudo-random u64s for property checks
+    fn sample_u64s(count: usize) -> impl Iterator<Item = u64> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..count).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
+
+    #[test]
+    fn mul_div_rounds_down() {
+        assert_eq!(mul_div(7, 3, 2), Some(10));
+        assert_eq!(mul_div(6, 3, 2), Some(9));
+        assert_eq!(mul_div(0, u128::MAX, 1), Some(0));
+    }
+
+    #[test]
+    fn mul_div_matches_native_math_when_the_product_fits() {
+        let values: Vec<u64> = sample_u64s(300).collect();
+        for chunk in values.chunks(3) {
+            let (a, b, c) = (chunk[0] as u128, chunk[1] as u128, chunk[2] as u128 | 1);
+            assert_eq!(mul_div(a, b, c), Some(a * b / c));
+        }
+    }
+
+    #[test]
+    fn mul_div_keeps_the_full_256_bit_product() {
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 1 << 64, 1 << 65), Some(u128::MAX >> 1));
+        assert_eq!(mul_div(1 << 100, 1 << 100, 1 << 90), Some(1 << 110));
+    }
+
+    #[test]
+    fn mul_div_rejects_zero_divisor_and_overflowing_quotient() {
+        assert_eq!(mul_div(1, 1, 0), None);
+        assert_eq!(mul_div(u128::MAX, 2, 1), None);
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX - 1), None);
+    }
+
+    #[test]
+    fn mul_div_ceil_rounds_up_only_on_a_remainder() {
+        assert_eq!(mul_div_ceil(7, 3, 2), Some(11));
+        assert_eq!(mul_div_ceil(6, 3, 2), Some(9));
+        assert_eq!(mul_div_ceil(0, 5, 3), Some(0));
+        // Remainders hidden above the low 128 bits of the product still round up
+        assert_eq!(mul_div_ceil(u128::MAX, u128::MAX, u128::MAX - 1), None);
+        assert_eq!(mul_div_ceil(1 << 127, 3, 1 << 127), Some(3));
+        assert_eq!(mul_div_ceil((1 << 127) + 1, 2, 1 << 127), Some(3));
+    }
+
+    #[test]
+    fn mul_div_ceil_matches_native_math_when_the_product_fits() {
+        let values: Vec<u64> = sample_u64s(300).collect();
+        for chunk in values.chunks(3) {
+            let (a, b, c) = (chunk[0] as u128, chunk[1] as u128, chunk[2] as u128 | 1);
+            assert_eq!(mul_div_ceil(a, b, c), Some((a * b).div_ceil(c)));
+        }
+    }
+
+    #[test]
+    fn mul_div_ceil_rejects_a_quotient_rounded_past_u128() {
+        // (2^129 - 1) / 2 floors to u128::MAX with a remainder of one
+        let a = 97_223_533_405_982_418_132_392_744_980_505_203_273;
+        assert_eq!(mul_div(a, 7, 2), Some(u128::MAX));
+        assert_eq!(mul_div_ceil(a, 7, 2), None);
+    }
+
+    #[test]
+    fn stable_swap_d_of_a_balanced_pool_is_the_sum() {
+        for amp in [1, 100, MAX_AMP] {
+            let d = calculate_stable_swap_d(1_000_000, 1_000_000, amp).unwrap();
+            assert!(d.abs_diff(2_000_000) <= 1, "amp {amp}: D = {d}");
+        }
+    }
+
+    #[test]
+    fn stable_swap_d_sits_between_product_and_sum() {
+        // D ranges from 2 * sqrt(xy) as amp goes to 0 up to x + y as amp goes to infinity
+        let (x, y) = (1_000_000_000u128, 10_000_000u128);
+        let lower = 2 * integer_sqrt(x * y);
+        let mut previous = lower;
+        for amp in [1, 10, 100, 1_000, MAX_AMP] {
+            let d = calculate_stable_swap_d(x, y, amp).unwrap();
+            assert!(d >= previous && d <= x + y, "amp {amp}: D = {d}");
+            previous = d;
+        }
+    }
+
+    #[test]
+    fn stable_swap_y_inverts_d() {
+        let balances = sample_u64s(40).collect::<Vec<_>>();
+        for pair in balances.chunks(2) {
+            // Keep the pair within 1000:1 so Newton's method is in its working range
+            let x = (pair[0] >> 20) as u128 + 1_000_000;
+            let y = x * (pair[1] % 1_000 + 1) as u128 / 10;
+            for amp in [1, 100, 10_000] {
+                let d = calculate_stable_swap_d(x, y, amp).unwrap();
+                let solved_y = calculate_stable_swap_y(x, d, amp).unwrap();
+                // D is only exact to one unit, which moves y by up to dy/dD units
+                assert!(solved_y.abs_diff(y) <= y / 1_000_000_000 + 2, "x {x}, y {y}, amp {amp}: y' = {solved_y}");
+            }
+        }
+    }
+
+    #[test]
+    fn stable_swap_converges_at_the_largest_normalized_balances() {
+        // u64::MAX reserves of a 6-decimal mint scaled up to pair with an 18-decimal one
+        let (multiplier, _) = precision_multipliers(6, 18).unwrap();
+        let balance = u64::MAX as u128 * multiplier;
+        for amp in [MIN_AMP, MAX_AMP] {
+            let d = calculate_stable_swap_d(balance, balance, amp).unwrap();
+            assert!(d.abs_diff(2 * balance) <= 1);
+            assert!(calculate_stable_swap_y(balance, d, amp).unwrap().abs_diff(balance) <= 1);
+        }
+    }
+
+    #[test]
+    fn stable_swap_d_rejects_one_empty_side() {
+        assert_eq!(calculate_stable_swap_d(0, 0, 100), Some(0));
+        assert_eq!(calculate_stable_swap_d(0, 1_000, 100), None);
+        assert_eq!(calculate_stable_swap_d(1_000, 1_000, 0), None);
+        assert_eq!(calculate_stable_swap_y(0, 1_000, 100), None);
+    }
+
+    #[test]
+    fn stable_swap_output_rounds_in_favour_of_the_pool() {
+        let multipliers = (1, 1);
+        for amp in [1, 100, 10_000] {
+            // A balanced pool never pays out more than it takes in
+            let output = calculate_stable_swap_output(1_000_000, 1_000_000_000, 1_000_000_000, multipliers, amp, 0).unwrap();
+            assert!(output < 1_000_000, "amp {amp}: {output}");
+
+            // and beats the constant product price, more so the higher amp is
+            let constant_product = calculate_constant_product_swap_output(1_000_000, 1_000_000_000, 1_000_000_000, 0).unwrap();
+            assert!(output >= constant_product, "amp {amp}: {output} < {constant_product}");
+        }
+    }
+
+    #[test]
+    fn stable_swap_input_covers_the_requested_output() {
+        let multipliers = precision_multipliers(6, 9).unwrap();
+        for (output_amount, amp, fee_bps) in [(1, 100, 30), (12_345, 1, 0), (5_000_000, 2_000, 100), (400_000_000, 50, 5)] {
+            let input = calculate_stable_swap_input(output_amount, 1_000_000_000, 1_000_000_000_000, multipliers, amp, fee_bps).unwrap();
+            let output = calculate_stable_swap_output(input, 1_000_000_000, 1_000_000_000_000, multipliers, amp, fee_bps).unwrap();
+            assert!(output >= output_amount, "asked for {output_amount}, {input} in gives {output}");
+        }
+    }
+
+    /// e in WAD
+    const E_WAD: i128 = 2_718_281_828_459_045_235;
+
+    fn side(reserve: u64, weight: u16) -> SwapSide {
+        SwapSide { reserve, decimals: 6, weight }
+    }
+
+    #[test]
+    fn ln_wad_hits_known_values() {
+        assert_eq!(ln_wad(WAD), Some(0));
+        assert!(ln_wad(2 * WAD).unwrap().abs_diff(LN_2_WAD) <= 100);
+        assert!(ln_wad(E_WAD).unwrap().abs_diff(WAD) <= 100);
+        assert!(ln_wad(WAD / 2).unwrap().abs_diff(-LN_2_WAD) <= 100);
+        // ln(1e-18) = -41.446531673892822312 and ln(1e20) = 46.051701859880913680
+        assert!(ln_wad(1).unwrap().abs_diff(-41_446_531_673_892_822_312) <= 100);
+        assert!(ln_wad(100 * WAD).unwrap().abs_diff(4_605_170_185_988_091_368) <= 100);
+    }
+
+    #[test]
+    fn ln_wad_rejects_non_positive_input() {
+        assert_eq!(ln_wad(0), None);
+        assert_eq!(ln_wad(-WAD), None);
+    }
+
+    #[test]
+    fn exp_wad_hits_known_values() {
+        assert_eq!(exp_wad(0), Some(WAD));
+        assert!(exp_wad(WAD).unwrap().abs_diff(E_WAD) <= 100);
+        assert!(exp_wad(LN_2_WAD).unwrap().abs_diff(2 * WAD) <= 100);
+        assert!(exp_wad(-LN_2_WAD).unwrap().abs_diff(WAD / 2) <= 100);
+    }
+
+    #[test]
+    fn exp_wad_saturates_low_and_rejects_high() {
+        assert_eq!(exp_wad(MIN_EXP_WAD - 1), Some(0));
+        assert!(exp_wad(MAX_EXP_WAD).is_some());
+        assert_eq!(exp_wad(MAX_EXP_WAD + 1), None);
+    }
+
+    #[test]
+    fn exp_wad_inverts_ln_wad() {
+        for x in sample_u64s(50).map(|value| value as i128 * 1_000) {
+            let round_trip = exp_wad(ln_wad(x).unwrap()).unwrap();
+            assert!(round_trip.abs_diff(x) <= (x / WAD * MAX_POW_RELATIVE_ERROR + 2) as u128, "{x} -> {round_trip}");
+        }
+    }
+
+    #[test]
+    fn pow_wad_up_rounds_up_within_its_error_bound() {
+        for (base, exponent, exact) in [
+            (4 * WAD, WAD / 2, 2 * WAD),
+            (WAD / 4, WAD / 2, WAD / 2),
+            (3 * WAD, 2 * WAD, 9 * WAD),
+            (2 * WAD, WAD, 2 * WAD),
+            (WAD, 7 * WAD, WAD),
+        ] {
+            let power = pow_wad_up(base, exponent).unwrap();
+            assert!(power >= exact, "{base}^{exponent} = {power} < {exact}");
+            assert!(power - exact <= 2 * (exact / WAD * MAX_POW_RELATIVE_ERROR + 2), "{base}^{exponent} = {power}");
+        }
+    }
+
+    #[test]
+    fn equal_weights_price_like_constant_product() {
+        for (amount, fee_bps) in [(1_000, 0), (1_000_000, 30), (250_000_000, 100)] {
+            let weighted = calculate_weighted_swap_output(amount, side(1_000_000_000, 5_000), side(2_000_000_000, 5_000), fee_bps).unwrap();
+            let constant_product = calculate_constant_product_swap_output(amount, 1_000_000_000, 2_000_000_000, fee_bps).unwrap();
+            // Never more than the exact curve, and only the rounding margin less
+            assert!(weighted <= constant_product, "{weighted} > {constant_product}");
+            assert!(constant_product - weighted <= constant_product / 1_000_000_000 + 2, "{weighted} vs {constant_product}");
+        }
+    }
+
+    #[test]
+    fn weighted_input_covers_the_requested_output() {
+        for (output_amount, weight_in, weight_out, fee_bps) in [(1, 8_000, 2_000, 30), (12_345, 2_000, 8_000, 0), (100_000_000, 5_000, 5_000, 100)] {
+            let input = side(1_000_000_000, weight_in);
+            let output = side(1_000_000_000, weight_out);
+            let amount_in = calculate_weighted_swap_input(output_amount, input, output, fee_bps).unwrap();
+            let amount_out = calculate_weighted_swap_output(amount_in, input, output, fee_bps).unwrap();
+            assert!(amount_out >= output_amount, "asked for {output_amount}, {amount_in} in gives {amount_out}");
+        }
+    }
+
+    #[test]
+    fn weighted_swaps_enforce_the_ratio_limits() {
+        let reserve = 1_000_000_000;
+        let max_in = reserve * MAX_WEIGHTED_IN_RATIO_BPS / FEE_BPS_DENOMINATOR;
+        let max_out = reserve * MAX_WEIGHTED_OUT_RATIO_BPS / FEE_BPS_DENOMINATOR;
+        assert!(calculate_weighted_swap_output(max_in, side(reserve, 5_000), side(reserve, 5_000), 0).is_some());
+        assert_eq!(calculate_weighted_swap_output(max_in + 1, side(reserve, 5_000), side(reserve, 5_000), 0), None);
+        assert!(calculate_weighted_swap_input(max_out, side(reserve, 5_000), side(reserve, 5_000), 0).is_some());
+        assert_eq!(calculate_weighted_swap_input(max_out + 1, side(reserve, 5_000), side(reserve, 5_000), 0), None);
+    }
+
+    #[test]
+    fn weighted_initial_lp_rounds_down_to_the_geometric_mean() {
+        let lp = calculate_weighted_initial_lp(1_000_000, 4_000_000, 5_000, 5_000).unwrap();
+        assert!((1_999_990..=2_000_000).contains(&lp), "{lp}");
+
+        // 80/20: 2^0.8 * 1024^0.2 = 1.741 * 4 units of 1e6
+        let lp = calculate_weighted_initial_lp(2_000_000, 1_024_000_000, 8_000, 2_000).unwrap();
+        let exact = 6_964_404;
+        assert!((exact - 10..=exact).contains(&lp), "{lp}");
+
+        assert_eq!(calculate_weighted_initial_lp(0, 1_000, 5_000, 5_000), None);
+    }
+}
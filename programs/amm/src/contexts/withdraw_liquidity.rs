@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Token2022, TokenInterface, TokenAccount, Mint, burn, Burn},
+};
+
+use crate::{
+    constants::{POOL_AUTHORITY_SEED, AMM_SEED, OBSERVATIONS_SEED},
+    contexts::oracle::update_oracle,
+    contexts::transfers::transfer_checked_with_hook,
+    contexts::utils::get_transfer_fee,
+    errors::AmmError,
+    events::LiquidityWithdrawn,
+    state::{Amm, CurveType, Observations, Pool},
+};
+
+#[derive(Accounts)]
+#[instruction(lp_amount: u64, min_amount_a: u64, min_amount_b: u64)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(
+        seeds = [
+            AMM_SEED,
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub amm: Box<Account<'info, Amm>>,
+
+    #[account(
+        mut,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+        constraint = !pool.locked @ AmmError::PoolLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+
+    /// CHECK: Pool authority PDA
+    #[account(
+        seeds = [
+            pool.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            POOL_AUTHORITY_SEED,
+        ],
+        bump = pool.pool_authority_bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_a,
+        associated_token::authority = user,
+        associated_token::token_program = token_program_a,
+    )]
+    pub user_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint_b,
+        associated_token::authority = user,
+        associated_token::token_program = token_program_b,
+    )]
+    pub user_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidMint
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user.is_signer @ AmmError::NotSigner
+    )]
+    pub user: Signer<'info>,
+
+    /// Solana ecosystem accounts
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token2022>,
+    /// Token program of mint A, either SPL Token or Token-2022
+    #[account(address = pool.token_program_a @ AmmError::InvalidTokenProgram)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    /// Token program of mint B, either SPL Token or Token-2022
+    #[account(address = pool.token_program_b @ AmmError::InvalidTokenProgram)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    // Transfer hook accounts of whichever mints have a hook are passed as remaining accounts
+}
+
+impl<'info> WithdrawLiquidity<'info> {
+    pub fn withdraw_liquidity(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        lp_amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+    ) -> Result<()> {
+        msg!("Withdrawing {} LP tokens", lp_amount);
+        require!(lp_amount > 0, AmmError::InvalidAmount);
+        require!(self.pool.curve_type != CurveType::Concentrated, AmmError::InvalidCurve);
+
+        // Accrue the TWAP accumulators on the reserves before they change
+        update_oracle(
+            &mut self.pool,
+            &mut self.observations,
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        )?;
+        
+        // Work out the pro-rata share of each vault before anything moves
+        let (amount_a, amount_b) = self.calculate_withdraw_amounts(lp_amount)?;
+        msg!("Withdraw amounts: A = {}, B = {}", amount_a, amount_b);
+
+        // Check slippage against what actually lands in the user's accounts
+        let received_a = amount_a - get_transfer_fee(&self.mint_a.to_account_info(), amount_a)?;
+        let received_b = amount_b - get_transfer_fee(&self.mint_b.to_account_info(), amount_b)?;
+        require!(received_a >= min_amount_a, AmmError::SlippageExceeded);
+        require!(received_b >= min_amount_b, AmmError::SlippageExceeded);
+
+        // Burn the LP tokens first so the share is settled before paying out
+        self.burn_lp_tokens(lp_amount)?;
+        self.pool.total_liquidity = self.pool.total_liquidity.saturating_sub(lp_amount);
+
+        // Pay out both sides from the pool vaults
+        if amount_a > 0 {
+            self.transfer_from_pool_to_user(remaining_accounts, amount_a, true)?;
+        }
+        if amount_b > 0 {
+            self.transfer_from_pool_to_user(remaining_accounts, amount_b, false)?;
+        }
+
+        msg!("Withdraw completed successfully");
+        emit!(LiquidityWithdrawn {
+            amm: self.amm.key(),
+            pool: self.pool.key(),
+            user: self.user.key(),
+            amount_a,
+            amount_b,
+            liquidity: lp_amount,
+            total_liquidity: self.pool.total_liquidity,
+        });
+        Ok(())
+    }
+
+    fn calculate_withdraw_amounts(&self, lp_amount: u64) -> Result<(u64, u64)> {
+        let total_lp_supply = self.lp_mint.supply as u128;
+        require!(total_lp_supply > 0, AmmError::InsufficientLiquidity);
+        require!(lp_amount as u128 <= total_lp_supply, AmmError::InvalidAmount);
+
+        // Uncollected protocol fees belong to the treasury, not to the LPs
+        let (reserve_a, reserve_b) = self.pool.reserves(
+            self.pool_account_a.amount,
+            self.pool_account_b.amount,
+        );
+
+        let lp = lp_amount as u128;
+        let reserve_a = reserve_a as u128;
+        let reserve_b = reserve_b as u128;
+
+        // share = lp_amount * reserve / total_lp_supply, rounded down in favour of the pool
+        let amount_a = lp
+            .checked_mul(reserve_a)
+            .and_then(|v| v.checked_div(total_lp_supply))
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+        let amount_b = lp
+            .checked_mul(reserve_b)
+            .and_then(|v| v.checked_div(total_lp_supply))
+            .ok_or_else(|| error!(AmmError::InvalidAmount))?;
+
+        // Burning LP for nothing is never what the caller wants
+        require!(amount_a > 0 || amount_b > 0, AmmError::InsufficientLiquidity);
+
+        // Both are bounded by the vault balances, so they always fit in u64
+        Ok((amount_a as u64, amount_b as u64))
+    }
+
+    fn burn_lp_tokens(&self, lp_amount: u64) -> Result<()> {
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.lp_mint.to_account_info(),
+                    from: self.user_lp_account.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
+
+        Ok(())
+    }
+
+    fn transfer_from_pool_to_user(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+        is_token_a: bool,
+    ) -> Result<()> {
+        // Get pool authority bump from pool state
+        let pool_authority_bump = self.pool.pool_authority_bump;
+        
+        let pool_key = self.pool.key();
+        let mint_a_key = self.mint_a.key();
+        let mint_b_key = self.mint_b.key();
+        
+        // Create the signer seeds for the pool authority PDA
+        let authority_seeds = &[
+            pool_key.as_ref(),
+            mint_a_key.as_ref(),
+            mint_b_key.as_ref(),
+            POOL_AUTHORITY_SEED,
+            &[pool_authority_bump],
+        ];
+        
+        let (token_program, from, mint, to, decimals) = if is_token_a {
+            (&self.token_program_a, &self.pool_account_a, &self.mint_a, &self.user_account_a, self.mint_a.decimals)
+        } else {
+            (&self.token_program_b, &self.pool_account_b, &self.mint_b, &self.user_account_b, self.mint_b.decimals)
+        };
+        
+        // Transfer from pool to user with transfer hook, signed by the pool authority
+        transfer_checked_with_hook(
+            &token_program.to_account_info(),
+            &from.to_account_info(),
+            &mint.to_account_info(),
+            &to.to_account_info(),
+            &self.pool_authority.to_account_info(),
+            remaining_accounts,
+            amount,
+            decimals,
+            &[&authority_seeds[..]],
+        )
+    }
+}
@@ -24,4 +24,56 @@ pub enum AmmError {
     NotSigner,
     #[msg("Invalid pool authority")]
     InvalidPoolAuthority,
+    #[msg("Invalid SOL fee collector")]
+    InvalidFeeCollector,
+    #[msg("Invalid swap route")]
+    InvalidRoute,
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+    #[msg("Invalid token program")]
+    InvalidTokenProgram,
+    #[msg("Mints must be ordered so that mint_a < mint_b")]
+    MintsNotSorted,
+    #[msg("Trade fee is not one of the configured fee tiers")]
+    InvalidFeeTier,
+    #[msg("Not enough price history for the requested TWAP window")]
+    InsufficientPriceHistory,
+    #[msg("Invalid amplification coefficient or ramp")]
+    InvalidAmplification,
+    #[msg("Operation not supported by the pool's curve")]
+    InvalidCurve,
+    #[msg("Invalid pool weights")]
+    InvalidWeights,
+    #[msg("Invalid tick, tick range or tick spacing")]
+    InvalidTickRange,
+    #[msg("Invalid sqrt price")]
+    InvalidSqrtPrice,
+    #[msg("Invalid tick array")]
+    InvalidTickArray,
+    #[msg("A tick array the instruction needs was not passed")]
+    TickArrayMissing,
+    #[msg("Position still holds liquidity or uncollected fees")]
+    PositionNotEmpty,
+    #[msg("Signer does not own the position")]
+    InvalidPositionOwner,
+    #[msg("Pool is locked by an outstanding flash loan")]
+    PoolLocked,
+    #[msg("Flash loan is not repaid later in the transaction")]
+    FlashLoanNotRepaid,
+    #[msg("No flash loan is outstanding on the pool")]
+    NoFlashLoan,
+    #[msg("The AMM or pool is paused")]
+    Paused,
+    #[msg("Signer is not the pauser")]
+    UnauthorizedPauser,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Signer is not the fee manager")]
+    UnauthorizedFeeManager,
+    #[msg("Token account is not owned by the treasury")]
+    InvalidTreasury,
+    #[msg("Pending parameter change does not belong to this AMM")]
+    InvalidParamChange,
+    #[msg("The parameter change delay has not elapsed yet")]
+    TimelockNotElapsed,
 } 
\ No newline at end of file
@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use crate::state::{CurveType, ParamChange, Role};
+
+#[event]
+pub struct ConfigUpdated {
+    /// The program-wide config
+    pub config: Pubkey,
+    /// Admin of the config
+    pub admin: Pubkey,
+    /// Default collector for fees taken by the protocol
+    pub fee_collector: Pubkey,
+    /// LP trading fees pools may be created with, in basis points
+    pub fee_tiers: Vec<u16>,
+}
+
+#[event]
+pub struct AmmCreated {
+    /// The new AMM
+    pub amm: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    /// Admin of the AMM, initially holding every role
+    pub admin: Pubkey,
+    /// SOL fee charged per swap, in lamports
+    pub sol_fee: u64,
+    /// Account the SOL fee is sent to
+    pub sol_fee_collector: Pubkey,
+    /// Lowest LP trading fee pools may be created with, in basis points
+    pub min_trade_fee_bps: u16,
+    /// Highest LP trading fee pools may be created with, in basis points
+    pub max_trade_fee_bps: u16,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub amm: Pubkey,
+    /// The admin making the proposal
+    pub admin: Pubkey,
+    /// The proposed admin; default when a proposal is withdrawn
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub amm: Pubkey,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AmmMadeImmutable {
+    pub amm: Pubkey,
+    /// The admin who made the AMM immutable
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub amm: Pubkey,
+    pub role: Role,
+    /// New holder of the role; the admin when it was revoked
+    pub holder: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub amm: Pubkey,
+    /// The pool paused or unpaused; none when the switch is the AMM-wide one
+    pub pool: Option<Pubkey>,
+    pub paused: bool,
+    /// The pauser who flipped the switch
+    pub pauser: Pubkey,
+}
+
+/// Fee settings of an AMM after an executed parameter change
+#[event]
+pub struct FeeUpdated {
+    pub amm: Pubkey,
+    /// SOL fee charged per swap, in lamports
+    pub sol_fee: u64,
+    /// Account the SOL fee is sent to
+    pub sol_fee_collector: Pubkey,
+    /// Lowest LP trading fee new pools may be created with, in basis points
+    pub min_trade_fee_bps: u16,
+    /// Highest LP trading fee new pools may be created with, in basis points
+    pub max_trade_fee_bps: u16,
+    /// Protocol's share of the trade fee, in basis points
+    pub protocol_fee_share_bps: u16,
+    /// Fee on flash loans, in basis points of the amount borrowed
+    pub flash_loan_fee_bps: u16,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub lp_mint: Pubkey,
+    /// Position of the pool in the on-chain registry
+    pub index: u64,
+    pub curve_type: CurveType,
+    /// LP trading fee, in basis points
+    pub trade_fee_bps: u16,
+    /// StableSwap amplification coefficient, zero on other curves
+    pub amp: u64,
+    /// Weighted pool weights in basis points, zero on other curves
+    pub weight_a: u16,
+    pub weight_b: u16,
+    /// Concentrated pool tick spacing and initial price, zero on other curves
+    pub tick_spacing: u16,
+    pub sqrt_price_x64: u128,
+}
+
+#[event]
+pub struct PoolVaultsCreated {
+    pub pool: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    /// The depositor
+    pub user: Pubkey,
+    /// Amount of A sent by the depositor, including any transfer fee
+    pub amount_a: u64,
+    /// Amount of B sent by the depositor, including any transfer fee
+    pub amount_b: u64,
+    /// LP tokens minted to the depositor, net of the liquidity locked on a first deposit
+    pub liquidity: u64,
+    /// LP supply after the deposit, including locked liquidity
+    pub total_liquidity: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    /// The withdrawer
+    pub user: Pubkey,
+    /// Amount of A sent out of the pool
+    pub amount_a: u64,
+    /// Amount of B sent out of the pool
+    pub amount_b: u64,
+    /// LP tokens burned
+    pub liquidity: u64,
+    /// LP supply after the withdrawal
+    pub total_liquidity: u64,
+}
+
+#[event]
+pub struct Swapped {
+    /// The AMM the pool belongs to
+    pub amm: Pubkey,
+    /// The pool that was traded against
+    pub pool: Pubkey,
+    /// The trader
+    pub user: Pubkey,
+    /// true for A to B, false for B to A
+    pub swap_a: bool,
+    /// Amount of the input token sent by the user
+    pub input_amount: u64,
+    /// Amount of the output token received by the user
+    pub output_amount: u64,
+    /// Token-2022 transfer fee withheld from the input on its way into the pool
+    pub input_transfer_fee: u64,
+    /// Token-2022 transfer fee withheld from the output on its way to the user
+    pub output_transfer_fee: u64,
+    /// LP trading fee taken from the input, in input token units
+    pub trade_fee: u64,
+    /// Part of the trade fee set aside for the protocol, in input token units
+    pub protocol_fee: u64,
+    /// SOL fee charged to the user, in lamports
+    pub sol_fee: u64,
+    /// Account the SOL fee was sent to
+    pub sol_fee_collector: Pubkey,
+    /// Pool reserves of A before the swap, excluding uncollected protocol fees
+    pub reserve_a_before: u64,
+    /// Pool reserves of B before the swap, excluding uncollected protocol fees
+    pub reserve_b_before: u64,
+    /// Pool reserves of A after the swap, excluding uncollected protocol fees
+    pub reserve_a_after: u64,
+    /// Pool reserves of B after the swap, excluding uncollected protocol fees
+    pub reserve_b_after: u64,
+}
+
+#[event]
+pub struct ParamChangeQueued {
+    /// The AMM the change applies to
+    pub amm: Pubkey,
+    /// The pending change account
+    pub pending_change: Pubkey,
+    /// The new value
+    pub change: ParamChange,
+    /// Earliest time the change can be executed, as a unix timestamp
+    pub eta: i64,
+    /// Account that queued the change
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct ParamChangeExecuted {
+    /// The AMM the change was applied to
+    pub amm: Pubkey,
+    /// The pending change account, closed by the execution
+    pub pending_change: Pubkey,
+    /// The value now in effect
+    pub change: ParamChange,
+}
+
+#[event]
+pub struct ParamChangeCancelled {
+    /// The AMM the change would have applied to
+    pub amm: Pubkey,
+    /// The pending change account, closed by the cancellation
+    pub pending_change: Pubkey,
+    /// The value that will not take effect
+    pub change: ParamChange,
+    /// Account that cancelled the change
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct AmplificationRampStarted {
+    pub pool: Pubkey,
+    pub amp_initial: u64,
+    pub amp_target: u64,
+    /// Unix timestamps the ramp runs between
+    pub ramp_start: i64,
+    pub ramp_end: i64,
+}
+
+#[event]
+pub struct TickArrayInitialized {
+    pub pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+}
+
+#[event]
+pub struct PositionOpened {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+}
+
+#[event]
+pub struct PositionLiquidityChanged {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    /// Liquidity added (positive) or removed (negative)
+    pub liquidity_delta: i128,
+    /// Amount of A that entered or left the vault, excluding any transfer fee
+    pub amount_a: u64,
+    /// Amount of B that entered or left the vault, excluding any transfer fee
+    pub amount_b: u64,
+    /// Liquidity of the position afterwards
+    pub liquidity: u128,
+}
+
+#[event]
+pub struct PositionFeesCollected {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct FlashLoanBorrowed {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct FlashLoanRepaid {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    /// Principal returned
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Flash loan fee paid on top
+    pub fee_a: u64,
+    pub fee_b: u64,
+}
+
+#[event]
+pub struct ProtocolFeesCollected {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Treasury token accounts the fees were sent to
+    pub treasury_account_a: Pubkey,
+    pub treasury_account_b: Pubkey,
+}
@@ -2,6 +2,8 @@ use anchor_lang::prelude::*;
 
 pub use errors::*;
 pub mod errors;
+pub use events::*;
+pub mod events;
 pub use state::*;
 pub mod state;
 pub mod constants;
@@ -15,50 +17,133 @@ declare_id!("H7dswT3BXcCEeVjjLWkfpBP2p5imuJy7Qaq9i5VCpoos");
 pub mod amm {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_tiers: Vec<u16>,
+    ) -> Result<()> {
+        msg!("Instruction: InitializeConfig");
+        let bump = ctx.bumps.config;
+        ctx.accounts.initialize_config(fee_tiers, bump)
+    }
+
+    pub fn update_fee_tiers(ctx: Context<UpdateConfig>, fee_tiers: Vec<u16>) -> Result<()> {
+        ctx.accounts.update_fee_tiers(fee_tiers)
+    }
+
+    pub fn update_fee_collector(ctx: Context<UpdateConfig>, fee_collector: Pubkey) -> Result<()> {
+        ctx.accounts.update_fee_collector(fee_collector)
+    }
+
+    pub fn update_config_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.update_config_admin(new_admin)
+    }
+
     pub fn create_amm(
         ctx: Context<CreateAmm>, 
         mint_a: Pubkey, 
         mint_b: Pubkey,
         sol_fee: u64,
-        sol_fee_collector: Pubkey,
+        sol_fee_collector: Option<Pubkey>,
+        min_trade_fee_bps: u16,
+        max_trade_fee_bps: u16,
     ) -> Result<()> {
         msg!("Instruction: CreateAmm");
         msg!("Mint A: {}", mint_a);
         msg!("Mint B: {}", mint_b);
         msg!("SOL fee: {}", sol_fee);
-        msg!("SOL fee collector: {}", sol_fee_collector);
+        msg!("SOL fee collector: {:?}", sol_fee_collector);
         msg!("AMM account key: {}", ctx.accounts.amm.key());
         msg!("Admin key: {}", ctx.accounts.admin.key());
         msg!("Authority key: {}", ctx.accounts.authority.key());
         
         // Validate SOL fee is within acceptable range (0-0.1 SOL)
-        require!(sol_fee > 0 && sol_fee <= 100_000_000, AmmError::InvalidFee); // Max 0.1 SOL
+        require!(sol_fee > 0 && sol_fee <= constants::MAX_SOL_FEE, AmmError::InvalidFee); // Max 0.1 SOL
         msg!("SOL fee validation passed");
         
+        // Validate the LP trading fee bounds pools will be created within
+        require!(
+            min_trade_fee_bps <= max_trade_fee_bps && max_trade_fee_bps <= constants::MAX_TRADE_FEE_BPS,
+            AmmError::InvalidFee
+        );
+        msg!("Trade fee bounds: {} - {} bps", min_trade_fee_bps, max_trade_fee_bps);
+        
         msg!("About to call create_amm on accounts");
-        ctx.accounts.create_amm(mint_a, mint_b, sol_fee, sol_fee_collector)?;
+        ctx.accounts.create_amm(
+            mint_a,
+            mint_b,
+            sol_fee,
+            sol_fee_collector,
+            min_trade_fee_bps,
+            max_trade_fee_bps,
+        )?;
         msg!("create_amm completed successfully");
         Ok(())
     }
 
-    pub fn update_admin(ctx: Context<UpdateAmm>, new_admin: Pubkey) -> Result<()> {
-        ctx.accounts.update_admin(new_admin)?;
+    pub fn propose_admin(ctx: Context<UpdateAmm>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)?;
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()?;
+        Ok(())
+    }
+
+    pub fn make_immutable(ctx: Context<UpdateAmm>) -> Result<()> {
+        ctx.accounts.make_immutable()?;
+        Ok(())
+    }
+
+    pub fn grant_role(ctx: Context<UpdateAmm>, role: Role, holder: Pubkey) -> Result<()> {
+        ctx.accounts.grant_role(role, holder)?;
         Ok(())
     }
 
-    pub fn update_fee(ctx: Context<UpdateFee>, new_sol_fee: u64) -> Result<()> {
-        ctx.accounts.update_fee(new_sol_fee)?;
+    pub fn revoke_role(ctx: Context<UpdateAmm>, role: Role) -> Result<()> {
+        ctx.accounts.revoke_role(role)?;
         Ok(())
     }
 
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        msg!("Instruction: Pause");
+        ctx.accounts.set_paused(true)
+    }
+
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        msg!("Instruction: Unpause");
+        ctx.accounts.set_paused(false)
+    }
+
+    pub fn queue_param_change(ctx: Context<QueueParamChange>, change: ParamChange) -> Result<()> {
+        ctx.accounts.queue_param_change(change, ctx.bumps.pending_change)
+    }
+
+    pub fn execute_param_change(ctx: Context<ExecuteParamChange>) -> Result<()> {
+        ctx.accounts.execute_param_change()
+    }
+
+    pub fn cancel_param_change(ctx: Context<CancelParamChange>) -> Result<()> {
+        ctx.accounts.cancel_param_change()
+    }
+
     pub fn create_pool(
-        ctx: Context<CreatePool>
+        ctx: Context<CreatePool>,
+        trade_fee_bps: u16,
+        curve_type: CurveType,
+        amp: u64,
+        weight_a: u16,
+        tick_spacing: u16,
+        sqrt_price_x64: u128,
     ) -> Result<()> {
         msg!("Instruction: CreatePool");
         msg!("Mint A: {}", ctx.accounts.mint_a.key());
         msg!("Mint B: {}", ctx.accounts.mint_b.key());
-        msg!("Token program: {}", ctx.accounts.token_program.key());
-        ctx.accounts.create_pool()?;
+        msg!("Token program A: {}", ctx.accounts.token_program_a.key());
+        msg!("Token program B: {}", ctx.accounts.token_program_b.key());
+        msg!("Trade fee: {} bps", trade_fee_bps);
+        msg!("Curve: {:?}", curve_type);
+        ctx.accounts.create_pool(trade_fee_bps, curve_type, amp, weight_a, tick_spacing, sqrt_price_x64)?;
         Ok(())
     }
 
@@ -74,23 +159,142 @@ pub mod amm {
         Ok(())
     }
 
-    pub fn deposit_liquidity(
-        ctx: Context<DepositLiquidity>, amount_a: u64, amount_b: u64
+    pub fn deposit_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositLiquidity<'info>>, max_amount_a: u64, max_amount_b: u64, min_lp_out: u64, deadline: Option<i64>
     ) -> Result<()> {
-        ctx.accounts.deposit_liquidity(amount_a, amount_b)
+        check_deadline(deadline)?;
+        ctx.accounts.deposit_liquidity(ctx.remaining_accounts, max_amount_a, max_amount_b, min_lp_out)
+    }
+
+    pub fn withdraw_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawLiquidity<'info>>, lp_amount: u64, min_amount_a: u64, min_amount_b: u64, deadline: Option<i64>
+    ) -> Result<()> {
+        check_deadline(deadline)?;
+        ctx.accounts.withdraw_liquidity(ctx.remaining_accounts, lp_amount, min_amount_a, min_amount_b)
     }
     
-    pub fn swap(
-        ctx: Context<Swap>,
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
         swap_a: bool,
         input_amount: u64,
         min_output_amount: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
+        check_deadline(deadline)?;
         ctx.accounts.swap(
+            ctx.remaining_accounts,
             swap_a,
             input_amount,
             min_output_amount,
             &ctx.bumps,
         )
     }
-} 
\ No newline at end of file
+
+    pub fn swap_tokens_for_exact_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        swap_a: bool,
+        output_amount: u64,
+        max_input_amount: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        check_deadline(deadline)?;
+        ctx.accounts.swap_tokens_for_exact_tokens(
+            ctx.remaining_accounts,
+            swap_a,
+            output_amount,
+            max_input_amount,
+        )
+    }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        hops: u8,
+        amount_in: u64,
+        min_output_amount: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        check_deadline(deadline)?;
+        ctx.accounts.swap_route(
+            ctx.remaining_accounts,
+            hops,
+            amount_in,
+            min_output_amount,
+        )
+    }
+
+    pub fn ramp_amplification(
+        ctx: Context<RampAmplification>,
+        target_amp: u64,
+        ramp_end: i64,
+    ) -> Result<()> {
+        ctx.accounts.ramp_amplification(target_amp, ramp_end)
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, window: i64) -> Result<Twap> {
+        ctx.accounts.get_twap(window)
+    }
+
+    pub fn initialize_tick_array(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
+        msg!("Instruction: InitializeTickArray");
+        ctx.accounts.initialize_tick_array(start_tick_index)
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+        msg!("Instruction: OpenPosition");
+        ctx.accounts.open_position(tick_lower, tick_upper, ctx.bumps.position)
+    }
+
+    pub fn increase_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyPosition<'info>>,
+        liquidity: u128,
+        max_amount_a: u64,
+        max_amount_b: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        check_deadline(deadline)?;
+        ctx.accounts.increase_liquidity(ctx.remaining_accounts, liquidity, max_amount_a, max_amount_b)
+    }
+
+    pub fn decrease_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyPosition<'info>>,
+        liquidity: u128,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        check_deadline(deadline)?;
+        ctx.accounts.decrease_liquidity(ctx.remaining_accounts, liquidity, min_amount_a, min_amount_b)
+    }
+
+    pub fn collect_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyPosition<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.collect_fees(ctx.remaining_accounts)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        ctx.accounts.close_position()
+    }
+
+    pub fn flash_borrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<()> {
+        msg!("Instruction: FlashBorrow");
+        ctx.accounts.flash_borrow(ctx.remaining_accounts, amount_a, amount_b)
+    }
+
+    pub fn flash_repay<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashLoan<'info>>,
+    ) -> Result<()> {
+        msg!("Instruction: FlashRepay");
+        ctx.accounts.flash_repay(ctx.remaining_accounts)
+    }
+
+    pub fn collect_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectProtocolFees<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.collect_protocol_fees(ctx.remaining_accounts)
+    }
+}
@@ -1,11 +1,27 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{
+        AMM_SEED,
+        MAX_FEE_TIERS,
+        OBSERVATION_CAPACITY,
+        OBSERVATION_INTERVAL,
+        POOL_AUTHORITY_SEED,
+        POSITION_SEED,
+        TICK_ARRAY_SEED,
+        TICK_ARRAY_SIZE,
+    },
+    contexts::utils::mul_div,
+};
+
 #[account]
 pub struct Amm {
     /// The unique pool identifier (fixed size array)
     pub pool_id: [u8; 64],
     /// Account that has admin authority over the AMM
     pub admin: Pubkey,
+    /// Proposed next admin, who takes over once they accept; default when none is pending
+    pub pending_admin: Pubkey,
     /// The SOL fee taken on each trade, in lamports (e.g., 0.05 SOL = 50,000,000 lamports)
     pub sol_fee: u64,
     /// SOL fee collector account
@@ -14,6 +30,18 @@ pub struct Amm {
     pub created: bool,
     /// Whether the AMM is immutable
     pub is_immutable: bool,
+    /// Lowest LP trading fee a pool may be created with, in basis points
+    pub min_trade_fee_bps: u16,
+    /// Highest LP trading fee a pool may be created with, in basis points
+    pub max_trade_fee_bps: u16,
+    /// Share of every trade fee kept for the protocol, in basis points of the fee
+    pub protocol_fee_share_bps: u16,
+    /// Fee on flash loans from the AMM's pools, in basis points of the amount borrowed
+    pub flash_loan_fee_bps: u16,
+    /// Keys holding the AMM's delegated roles
+    pub roles: AmmRoles,
+    /// Whether trading and new liquidity are stopped in every pool of the AMM
+    pub paused: bool,
 }
 
 impl Default for Amm {
@@ -21,16 +49,194 @@ impl Default for Amm {
         Self {
             pool_id: [0u8; 64],
             admin: Pubkey::default(),
+            pending_admin: Pubkey::default(),
             sol_fee: 0,
             sol_fee_collector: Pubkey::default(),
             created: false,
             is_immutable: false,
+            min_trade_fee_bps: 0,
+            max_trade_fee_bps: 0,
+            protocol_fee_share_bps: 0,
+            flash_loan_fee_bps: 0,
+            roles: AmmRoles::default(),
+            paused: false,
         }
     }
 }
 
 impl Amm {
-    pub const LEN: usize = 8 + 64 + 32 + 8 + 32 + 1 + 1; // 8 (discriminator) + 64 (pool_id) + 32 (admin) + 8 (sol_fee) + 32 (sol_fee_collector) + 1 (created) + 1 (is_immutable)
+    /// AMM address for a pair, whichever order the mints are given in
+    pub fn find_address(mint_x: &Pubkey, mint_y: &Pubkey) -> (Pubkey, u8) {
+        let (mint_a, mint_b) = sort_mints(*mint_x, *mint_y);
+        Pubkey::find_program_address(&[AMM_SEED, mint_a.as_ref(), mint_b.as_ref()], &crate::ID)
+    }
+
+    pub const LEN: usize = 8 + 64 + 32 + 32 + 8 + 32 + 1 + 1 + 2 + 2 + 2 + 2 + AmmRoles::LEN + 1; // 8 (discriminator) + 64 (pool_id) + 32 (admin) + 32 (pending_admin) + 8 (sol_fee) + 32 (sol_fee_collector) + 1 (created) + 1 (is_immutable) + 2 (min_trade_fee_bps) + 2 (max_trade_fee_bps) + 2 (protocol_fee_share_bps) + 2 (flash_loan_fee_bps) + 96 (roles) + 1 (paused)
+}
+
+/// Keys an AMM's admin delegates narrower powers to; each falls back to the admin when revoked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AmmRoles {
+    /// May change the AMM's fees within the bounds set by the admin
+    pub fee_manager: Pubkey,
+    /// May pause and unpause the AMM and its pools
+    pub pauser: Pubkey,
+    /// Owner of the token accounts protocol fees are collected to; never signs anything
+    pub treasury: Pubkey,
+}
+
+impl AmmRoles {
+    pub const LEN: usize = 32 + 32 + 32; // 32 (fee_manager) + 32 (pauser) + 32 (treasury)
+
+    /// Every role held by `holder`
+    pub fn all(holder: Pubkey) -> Self {
+        Self {
+            fee_manager: holder,
+            pauser: holder,
+            treasury: holder,
+        }
+    }
+
+    /// Hands every role `old_holder` has to `new_holder`, used when the admin changes so that
+    /// roles the old admin kept or got back on revocation follow the admin
+    pub fn transfer_held(&mut self, old_holder: Pubkey, new_holder: Pubkey) {
+        for holder in [&mut self.fee_manager, &mut self.pauser, &mut self.treasury] {
+            if *holder == old_holder {
+                *holder = new_holder;
+            }
+        }
+    }
+
+    pub fn get_mut(&mut self, role: Role) -> &mut Pubkey {
+        match role {
+            Role::FeeManager => &mut self.fee_manager,
+            Role::Pauser => &mut self.pauser,
+            Role::Treasury => &mut self.treasury,
+        }
+    }
+}
+
+/// Delegated role on an AMM, see `AmmRoles`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    FeeManager,
+    Pauser,
+    Treasury,
+}
+
+/// AMM parameter change that only takes effect after `PARAM_CHANGE_DELAY`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamChange {
+    /// New `sol_fee`, in lamports
+    SolFee(u64),
+    /// New `sol_fee_collector`
+    SolFeeCollector(Pubkey),
+    /// New `min_trade_fee_bps` and `max_trade_fee_bps`
+    TradeFeeBounds { min_bps: u16, max_bps: u16 },
+    /// New `protocol_fee_share_bps`
+    ProtocolFeeShare(u16),
+    /// New `flash_loan_fee_bps`
+    FlashLoanFee(u16),
+}
+
+impl ParamChange {
+    pub const LEN: usize = 1 + 32; // 1 (variant) + 32 (largest payload, SolFeeCollector)
+
+    /// Distinguishes the parameter changed, so that changes to different parameters queue independently
+    pub fn kind(&self) -> u8 {
+        match self {
+            ParamChange::SolFee(_) => 0,
+            ParamChange::SolFeeCollector(_) => 1,
+            ParamChange::TradeFeeBounds { .. } => 2,
+            ParamChange::ProtocolFeeShare(_) => 3,
+            ParamChange::FlashLoanFee(_) => 4,
+        }
+    }
+
+    /// Key allowed to queue or cancel the change: the fee manager for fee levels, the admin otherwise
+    pub fn authority(&self, amm: &Amm) -> Pubkey {
+        match self {
+            ParamChange::SolFee(_) | ParamChange::ProtocolFeeShare(_) | ParamChange::FlashLoanFee(_) => {
+                amm.roles.fee_manager
+            }
+            ParamChange::SolFeeCollector(_) | ParamChange::TradeFeeBounds { .. } => amm.admin,
+        }
+    }
+}
+
+/// A queued `ParamChange`, one per AMM and parameter
+#[account]
+pub struct PendingParamChange {
+    /// The AMM the change applies to
+    pub amm: Pubkey,
+    /// The new value
+    pub change: ParamChange,
+    /// Earliest time the change can be executed, as a unix timestamp
+    pub eta: i64,
+    /// Account that queued the change and gets the rent back
+    pub proposer: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PendingParamChange {
+    pub const LEN: usize = 8 + 32 + ParamChange::LEN + 8 + 32 + 1; // 8 (discriminator) + 32 (amm) + 33 (change) + 8 (eta) + 32 (proposer) + 1 (bump)
+}
+
+/// Program-wide settings, one per deployment
+#[account]
+#[derive(Default)]
+pub struct AmmConfig {
+    /// Account that has admin authority over the config
+    pub admin: Pubkey,
+    /// Default collector for fees taken by the protocol
+    pub fee_collector: Pubkey,
+    /// LP trading fees pools may be created with, in basis points; only the first `fee_tier_count` are set
+    pub fee_tiers: [u16; MAX_FEE_TIERS],
+    /// Number of entries of `fee_tiers` in use
+    pub fee_tier_count: u8,
+    /// Number of pools created so far, which is also the index of the next `PoolIndex`
+    pub pool_count: u64,
+    /// Config PDA bump
+    pub bump: u8,
+}
+
+impl AmmConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 2 * MAX_FEE_TIERS + 1 + 8 + 1; // 8 (discriminator) + 32 (admin) + 32 (fee_collector) + 2 * MAX_FEE_TIERS (fee_tiers) + 1 (fee_tier_count) + 8 (pool_count) + 1 (bump)
+
+    pub fn fee_tiers(&self) -> &[u16] {
+        &self.fee_tiers[..self.fee_tier_count as usize]
+    }
+
+    /// Lowest and highest fee tier, which bound the trade fees an AMM may allow
+    pub fn fee_tier_range(&self) -> (u16, u16) {
+        let tiers = self.fee_tiers();
+        (
+            tiers.iter().copied().min().unwrap_or_default(),
+            tiers.iter().copied().max().unwrap_or_default(),
+        )
+    }
+}
+
+/// Registry entry for one pool, seeded by its creation order so every pool can be
+/// listed by walking indexes 0..`AmmConfig::pool_count`
+#[account]
+#[derive(Default)]
+pub struct PoolIndex {
+    /// Position of the pool in the registry
+    pub index: u64,
+    /// The pool
+    pub pool: Pubkey,
+    /// AMM of the pool
+    pub amm: Pubkey,
+    /// Mint of token A
+    pub mint_a: Pubkey,
+    /// Mint of token B
+    pub mint_b: Pubkey,
+}
+
+impl PoolIndex {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 32 + 32; // 8 (discriminator) + 8 (index) + 32 (pool) + 32 (amm) + 32 (mint_a) + 32 (mint_b)
 }
 
 #[account()]
@@ -52,8 +258,466 @@ pub struct Pool {
     pub total_liquidity: u64,
     /// Pool authority bump for deterministic derivation
     pub pool_authority_bump: u8,
+    /// LP trading fee taken from the input of every swap, in basis points
+    pub trade_fee_bps: u16,
+    /// Protocol fees accrued in vault A and not yet collected
+    pub protocol_fees_a: u64,
+    /// Protocol fees accrued in vault B and not yet collected
+    pub protocol_fees_b: u64,
+    /// Token program that owns mint A (SPL Token or Token-2022)
+    pub token_program_a: Pubkey,
+    /// Token program that owns mint B (SPL Token or Token-2022)
+    pub token_program_b: Pubkey,
+    /// Time-weighted sum of the price of A in B (reserve_b / reserve_a) as Q64.64, wrapping
+    pub price_a_cumulative: u128,
+    /// Time-weighted sum of the price of B in A (reserve_a / reserve_b) as Q64.64, wrapping
+    pub price_b_cumulative: u128,
+    /// Unix timestamp the price accumulators were last brought up to
+    pub last_update_timestamp: i64,
+    /// Invariant used to price swaps
+    pub curve_type: CurveType,
+    /// StableSwap amplification at `amp_ramp_start`
+    pub amp_initial: u64,
+    /// StableSwap amplification from `amp_ramp_end` on
+    pub amp_target: u64,
+    /// Unix timestamp the current amplification ramp started
+    pub amp_ramp_start: i64,
+    /// Unix timestamp the current amplification ramp ends
+    pub amp_ramp_end: i64,
+    /// Weighted pools: weight of token A, in basis points of FEE_BPS_DENOMINATOR
+    pub weight_a: u16,
+    /// Weighted pools: weight of token B, in basis points of FEE_BPS_DENOMINATOR
+    pub weight_b: u16,
+    /// Concentrated pools: distance between initializable ticks
+    pub tick_spacing: u16,
+    /// Concentrated pools: tick of the current price, i.e. the largest tick at or below `sqrt_price_x64`
+    pub tick_current: i32,
+    /// Concentrated pools: square root of the price of A in B, Q64.64
+    pub sqrt_price_x64: u128,
+    /// Concentrated pools: liquidity of the positions whose range contains the current price
+    pub liquidity: u128,
+    /// Concentrated pools: LP fees in A earned per unit of liquidity since creation, Q64.64, wrapping
+    pub fee_growth_global_a_x64: u128,
+    /// Concentrated pools: LP fees in B earned per unit of liquidity since creation, Q64.64, wrapping
+    pub fee_growth_global_b_x64: u128,
+    /// Set while a flash loan is outstanding; swaps and liquidity changes are refused until it is repaid
+    pub locked: bool,
+    /// Principal of the outstanding flash loan in A
+    pub flash_loan_amount_a: u64,
+    /// Principal of the outstanding flash loan in B
+    pub flash_loan_amount_b: u64,
+    /// Whether trading and new liquidity are stopped in this pool; withdrawals stay open
+    pub paused: bool,
 }
 
 impl Pool {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 1; // 8 (discriminator) + 32 (amm) + 32 (mint_a) + 32 (mint_b) + 32 (vault_a) + 32 (vault_b) + 32 (lp_mint) + 8 (total_liquidity) + 1 (pool_authority_bump)
-} 
\ No newline at end of file
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 2 + 8 + 8 + 32 + 32 + 16 + 16 + 8 + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 2 + 4 + 16 + 16 + 16 + 16 + 1 + 8 + 8 + 1; // 8 (discriminator) + 32 (amm) + 32 (mint_a) + 32 (mint_b) + 32 (vault_a) + 32 (vault_b) + 32 (lp_mint) + 8 (total_liquidity) + 1 (pool_authority_bump) + 2 (trade_fee_bps) + 8 (protocol_fees_a) + 8 (protocol_fees_b) + 32 (token_program_a) + 32 (token_program_b) + 16 (price_a_cumulative) + 16 (price_b_cumulative) + 8 (last_update_timestamp) + 1 (curve_type) + 8 (amp_initial) + 8 (amp_target) + 8 (amp_ramp_start) + 8 (amp_ramp_end) + 2 (weight_a) + 2 (weight_b) + 2 (tick_spacing) + 4 (tick_current) + 16 (sqrt_price_x64) + 16 (liquidity) + 16 (fee_growth_global_a_x64) + 16 (fee_growth_global_b_x64) + 1 (locked) + 8 (flash_loan_amount_a) + 8 (flash_loan_amount_b) + 1 (paused)
+
+    /// Pool address of a pair under `amm`, whichever order the mints are given in
+    pub fn find_address(amm: &Pubkey, mint_x: &Pubkey, mint_y: &Pubkey) -> (Pubkey, u8) {
+        let (mint_a, mint_b) = sort_mints(*mint_x, *mint_y);
+        Pubkey::find_program_address(&[amm.as_ref(), mint_a.as_ref(), mint_b.as_ref()], &crate::ID)
+    }
+
+    /// Pool authority address of `pool`, whichever order the mints are given in
+    pub fn find_authority_address(pool: &Pubkey, mint_x: &Pubkey, mint_y: &Pubkey) -> (Pubkey, u8) {
+        let (mint_a, mint_b) = sort_mints(*mint_x, *mint_y);
+        Pubkey::find_program_address(
+            &[pool.as_ref(), mint_a.as_ref(), mint_b.as_ref(), POOL_AUTHORITY_SEED],
+            &crate::ID,
+        )
+    }
+
+    /// Whether trading and new liquidity are stopped, either here or for the whole AMM
+    pub fn is_paused(&self, amm: &Amm) -> bool {
+        self.paused || amm.paused
+    }
+
+    /// Vault balances available to the curve and to LPs, i.e. excluding uncollected protocol fees
+    pub fn reserves(&self, vault_a_amount: u64, vault_b_amount: u64) -> (u64, u64) {
+        (
+            vault_a_amount.saturating_sub(self.protocol_fees_a),
+            vault_b_amount.saturating_sub(self.protocol_fees_b),
+        )
+    }
+
+    /// (weight_a, weight_b) the spot price is scaled by; (1, 1) for curves without weights
+    pub fn price_weights(&self) -> (u16, u16) {
+        match self.curve_type {
+            CurveType::Weighted => (self.weight_a, self.weight_b),
+            _ => (1, 1),
+        }
+    }
+
+    /// (input weight, output weight) for the given direction
+    pub fn weights_in_out(&self, swap_a: bool) -> (u16, u16) {
+        if swap_a {
+            (self.weight_a, self.weight_b)
+        } else {
+            (self.weight_b, self.weight_a)
+        }
+    }
+
+    /// StableSwap amplification at `now`, moving linearly from `amp_initial` to
+    /// `amp_target` over the ramp
+    pub fn amplification(&self, now: i64) -> u64 {
+        if now >= self.amp_ramp_end || self.amp_ramp_end <= self.amp_ramp_start {
+            return self.amp_target;
+        }
+        if now <= self.amp_ramp_start {
+            return self.amp_initial;
+        }
+
+        // The amp difference is at most MAX_AMP and elapsed is below the ramp duration, which
+        // fits in an i64, so the product stays far inside a u128 and the change below the difference
+        let elapsed = (now - self.amp_ramp_start) as u128;
+        let duration = (self.amp_ramp_end - self.amp_ramp_start) as u128;
+        if self.amp_target >= self.amp_initial {
+            let change = (self.amp_target - self.amp_initial) as u128 * elapsed / duration;
+            self.amp_initial + change as u64
+        } else {
+            let change = (self.amp_initial - self.amp_target) as u128 * elapsed / duration;
+            self.amp_initial - change as u64
+        }
+    }
+
+    /// Q64.64 spot prices (A in B, B in A), saturating; None while the pool is empty
+    pub fn spot_prices(&self, reserve_a: u64, reserve_b: u64) -> Option<(u128, u128)> {
+        if self.curve_type == CurveType::Concentrated {
+            // The price is the square of the stored sqrt price
+            let price_a = mul_div(self.sqrt_price_x64, self.sqrt_price_x64, 1 << 64).unwrap_or(u128::MAX);
+            if price_a == 0 {
+                return None;
+            }
+            let price_b = mul_div(1 << 64, 1 << 64, price_a).unwrap_or(u128::MAX);
+            return Some((price_a, price_b));
+        }
+        if reserve_a == 0 || reserve_b == 0 {
+            return None;
+        }
+
+        // (reserve_b / weight_b) / (reserve_a / weight_a) and its inverse
+        let (weight_a, weight_b) = self.price_weights();
+        let scaled_a = reserve_a as u128 * weight_b as u128;
+        let scaled_b = reserve_b as u128 * weight_a as u128;
+        Some((
+            mul_div(scaled_b, 1 << 64, scaled_a).unwrap_or(u128::MAX),
+            mul_div(scaled_a, 1 << 64, scaled_b).unwrap_or(u128::MAX),
+        ))
+    }
+
+    /// Accrues the current prices over the time since the last update. Must run before
+    /// the reserves change, so each price is weighted by how long it actually held.
+    pub fn update_price_accumulators(&mut self, reserve_a: u64, reserve_b: u64, now: i64) {
+        let elapsed = now.saturating_sub(self.last_update_timestamp);
+        if elapsed <= 0 {
+            return;
+        }
+
+        if let Some((price_a, price_b)) = self.spot_prices(reserve_a, reserve_b) {
+            // Overflow is expected; TWAP readers take wrapping differences
+            self.price_a_cumulative = self
+                .price_a_cumulative
+                .wrapping_add(price_a.wrapping_mul(elapsed as u128));
+            self.price_b_cumulative = self
+                .price_b_cumulative
+                .wrapping_add(price_b.wrapping_mul(elapsed as u128));
+        }
+        self.last_update_timestamp = now;
+    }
+}
+
+/// Invariant a pool prices swaps with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurveType {
+    /// x * y = k
+    #[default]
+    ConstantProduct,
+    /// Curve-style StableSwap for correlated assets, see `Pool::amplification`
+    StableSwap,
+    /// Balancer-style x^w_a * y^w_b = k with the pool's `weight_a`/`weight_b`
+    Weighted,
+    /// Uniswap v3-style liquidity concentrated in tick ranges, see `Position`
+    Concentrated,
+}
+
+/// One initializable tick of a concentrated pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Tick {
+    /// Liquidity added to the pool when the price crosses the tick upwards, removed when it crosses downwards
+    pub liquidity_net: i128,
+    /// Liquidity of all positions bounded by the tick; zero means the tick is uninitialized
+    pub liquidity_gross: u128,
+    /// LP fee growth in A on the other side of the tick from the current price, Q64.64
+    pub fee_growth_outside_a_x64: u128,
+    /// LP fee growth in B on the other side of the tick from the current price, Q64.64
+    pub fee_growth_outside_b_x64: u128,
+}
+
+impl Tick {
+    pub const LEN: usize = 16 + 16 + 16 + 16; // 16 (liquidity_net) + 16 (liquidity_gross) + 16 (fee_growth_outside_a_x64) + 16 (fee_growth_outside_b_x64)
+}
+
+/// `TICK_ARRAY_SIZE` consecutive initializable ticks of a concentrated pool, starting
+/// at a multiple of `TICK_ARRAY_SIZE * tick_spacing`
+#[account]
+#[derive(Default)]
+pub struct TickArray {
+    /// The pool the ticks belong to
+    pub pool: Pubkey,
+    /// Tick index of `ticks[0]`
+    pub start_tick_index: i32,
+    /// The ticks, `tick_spacing` apart
+    pub ticks: [Tick; TICK_ARRAY_SIZE],
+}
+
+impl TickArray {
+    pub const LEN: usize = 8 + 32 + 4 + Tick::LEN * TICK_ARRAY_SIZE; // 8 (discriminator) + 32 (pool) + 4 (start_tick_index) + Tick::LEN * TICK_ARRAY_SIZE (ticks)
+
+    /// Tick array address of `pool` starting at `start_tick_index`
+    pub fn find_address(pool: &Pubkey, start_tick_index: i32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[TICK_ARRAY_SEED, pool.as_ref(), &start_tick_index.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+
+    /// Start of the tick array holding `tick`
+    pub fn start_tick_index(tick: i32, tick_spacing: u16) -> i32 {
+        let ticks_per_array = tick_spacing as i32 * TICK_ARRAY_SIZE as i32;
+        tick.div_euclid(ticks_per_array) * ticks_per_array
+    }
+}
+
+/// Liquidity one owner provides to a concentrated pool between two ticks
+#[account]
+#[derive(Default)]
+pub struct Position {
+    /// The pool
+    pub pool: Pubkey,
+    /// Account allowed to change the position and collect its fees
+    pub owner: Pubkey,
+    /// Lower end of the range, inclusive
+    pub tick_lower: i32,
+    /// Upper end of the range, exclusive
+    pub tick_upper: i32,
+    /// Liquidity the position provides while the price is inside its range
+    pub liquidity: u128,
+    /// Fee growth in A inside the range when the position was last updated, Q64.64
+    pub fee_growth_inside_a_last_x64: u128,
+    /// Fee growth in B inside the range when the position was last updated, Q64.64
+    pub fee_growth_inside_b_last_x64: u128,
+    /// LP fees in A earned and not yet collected
+    pub fees_owed_a: u64,
+    /// LP fees in B earned and not yet collected
+    pub fees_owed_b: u64,
+    /// Position PDA bump
+    pub bump: u8,
+}
+
+impl Position {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + 1; // 8 (discriminator) + 32 (pool) + 32 (owner) + 4 (tick_lower) + 4 (tick_upper) + 16 (liquidity) + 16 (fee_growth_inside_a_last_x64) + 16 (fee_growth_inside_b_last_x64) + 8 (fees_owed_a) + 8 (fees_owed_b) + 1 (bump)
+
+    /// Position address of `owner` in `pool` over [`tick_lower`, `tick_upper`)
+    pub fn find_address(pool: &Pubkey, owner: &Pubkey, tick_lower: i32, tick_upper: i32) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                POSITION_SEED,
+                pool.as_ref(),
+                owner.as_ref(),
+                &tick_lower.to_le_bytes(),
+                &tick_upper.to_le_bytes(),
+            ],
+            &crate::ID,
+        )
+    }
+}
+
+/// Snapshot of a pool's price accumulators
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Observation {
+    /// Unix timestamp of the snapshot
+    pub timestamp: i64,
+    /// `Pool::price_a_cumulative` at `timestamp`
+    pub price_a_cumulative: u128,
+    /// `Pool::price_b_cumulative` at `timestamp`
+    pub price_b_cumulative: u128,
+}
+
+impl Observation {
+    pub const LEN: usize = 8 + 16 + 16; // 8 (timestamp) + 16 (price_a_cumulative) + 16 (price_b_cumulative)
+}
+
+/// Ring buffer of accumulator snapshots for one pool, written at most once per `OBSERVATION_INTERVAL`
+#[account]
+pub struct Observations {
+    /// The pool observed
+    pub pool: Pubkey,
+    /// Slot of the most recent observation
+    pub index: u16,
+    /// Number of slots written so far, up to `OBSERVATION_CAPACITY`
+    pub count: u16,
+    /// The snapshots, oldest overwritten first
+    pub observations: [Observation; OBSERVATION_CAPACITY],
+}
+
+impl Observations {
+    pub const LEN: usize = 8 + 32 + 2 + 2 + Observation::LEN * OBSERVATION_CAPACITY; // 8 (discriminator) + 32 (pool) + 2 (index) + 2 (count) + Observation::LEN * OBSERVATION_CAPACITY (observations)
+
+    /// Stores a snapshot of the pool's accumulators unless the last one is less than
+    /// `OBSERVATION_INTERVAL` old
+    pub fn record(&mut self, pool: &Pool) {
+        if self.count > 0
+            && pool.last_update_timestamp - self.observations[self.index as usize].timestamp < OBSERVATION_INTERVAL
+        {
+            return;
+        }
+
+        let next = if self.count == 0 {
+            0
+        } else {
+            (self.index as usize + 1) % OBSERVATION_CAPACITY
+        };
+        self.observations[next] = Observation {
+            timestamp: pool.last_update_timestamp,
+            price_a_cumulative: pool.price_a_cumulative,
+            price_b_cumulative: pool.price_b_cumulative,
+        };
+        self.index = next as u16;
+        if (self.count as usize) < OBSERVATION_CAPACITY {
+            self.count += 1;
+        }
+    }
+
+    /// Newest observation taken at or before `timestamp`
+    pub fn at_or_before(&self, timestamp: i64) -> Option<Observation> {
+        (0..self.count as usize)
+            .map(|age| self.observations[(self.index as usize + OBSERVATION_CAPACITY - age) % OBSERVATION_CAPACITY])
+            .find(|observation| observation.timestamp <= timestamp)
+    }
+}
+
+/// Orders a pair the way AMMs and pools are keyed, i.e. `mint_a < mint_b`
+pub fn sort_mints(mint_x: Pubkey, mint_y: Pubkey) -> (Pubkey, Pubkey) {
+    if mint_x < mint_y {
+        (mint_x, mint_y)
+    } else {
+        (mint_y, mint_x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_observations() -> Observations {
+        Observations {
+            pool: Pubkey::default(),
+            index: 0,
+            count: 0,
+            observations: [Observation::default(); OBSERVATION_CAPACITY],
+        }
+    }
+
+    /// Records the pool once a second from `start` to `end`, as a pool trading every second would
+    fn record_every_second(observations: &mut Observations, start: i64, end: i64) {
+        let mut pool = Pool::default();
+        for now in start..=end {
+            pool.update_price_accumulators(1_000, 1_000, now);
+            observations.record(&pool);
+        }
+    }
+
+    #[test]
+    fn fee_tier_range_spans_the_configured_tiers_only() {
+        let mut config = AmmConfig {
+            admin: Pubkey::default(),
+            fee_collector: Pubkey::default(),
+            fee_tiers: [0; MAX_FEE_TIERS],
+            fee_tier_count: 3,
+            pool_count: 0,
+            bump: 0,
+        };
+        config.fee_tiers[..3].copy_from_slice(&[30, 5, 100]);
+        assert_eq!(config.fee_tier_range(), (5, 100));
+
+        // Unused slots are zero but don't count as a tier
+        config.fee_tier_count = 1;
+        assert_eq!(config.fee_tier_range(), (30, 30));
+    }
+
+    #[test]
+    fn accumulators_weight_each_price_by_how_long_it_held() {
+        let q64 = 1u128 << 64;
+        let mut pool = Pool::default();
+
+        // 1 B per A for 10 seconds, then 4 B per A for 30 seconds
+        pool.update_price_accumulators(1_000, 1_000, 10);
+        pool.update_price_accumulators(1_000, 4_000, 40);
+        assert_eq!(pool.price_a_cumulative, 10 * q64 + 30 * 4 * q64);
+        assert_eq!(pool.price_b_cumulative, 10 * q64 + 30 * q64 / 4);
+
+        // A second update in the same second changes nothing
+        pool.update_price_accumulators(1_000, 1_000, 40);
+        assert_eq!(pool.price_a_cumulative, 130 * q64);
+        assert_eq!(pool.last_update_timestamp, 40);
+    }
+
+    #[test]
+    fn accumulator_differences_survive_wrapping() {
+        let q64 = 1u128 << 64;
+        let mut pool = Pool {
+            price_a_cumulative: u128::MAX - q64,
+            ..Pool::default()
+        };
+        let start = pool.price_a_cumulative;
+
+        pool.update_price_accumulators(1_000, 2_000, 10);
+        assert!(pool.price_a_cumulative < start);
+        assert_eq!(pool.price_a_cumulative.wrapping_sub(start) / 10, 2 * q64);
+    }
+
+    #[test]
+    fn empty_pool_accrues_no_price() {
+        let mut pool = Pool::default();
+        pool.update_price_accumulators(0, 1_000, 10);
+        assert_eq!(pool.price_a_cumulative, 0);
+        assert_eq!(pool.price_b_cumulative, 0);
+        assert_eq!(pool.last_update_timestamp, 10);
+    }
+
+    #[test]
+    fn record_skips_observations_closer_than_the_interval() {
+        let mut observations = empty_observations();
+        record_every_second(&mut observations, 1, OBSERVATION_INTERVAL * 3);
+
+        assert_eq!(observations.count, 3);
+        assert_eq!(observations.observations[0].timestamp, 1);
+        assert_eq!(observations.observations[1].timestamp, 1 + OBSERVATION_INTERVAL);
+        assert_eq!(observations.observations[2].timestamp, 1 + 2 * OBSERVATION_INTERVAL);
+    }
+
+    #[test]
+    fn ring_covers_capacity_times_interval() {
+        let span = OBSERVATION_INTERVAL * OBSERVATION_CAPACITY as i64;
+        let now = 3 * span;
+        let mut observations = empty_observations();
+        record_every_second(&mut observations, 1, now);
+
+        assert_eq!(observations.count as usize, OBSERVATION_CAPACITY);
+        // The oldest surviving observation is one interval short of the full span
+        let oldest_window = span - OBSERVATION_INTERVAL;
+        let start = observations.at_or_before(now - oldest_window).unwrap();
+        assert!(now - start.timestamp >= oldest_window);
+    }
+
+    #[test]
+    fn window_longer_than_the_ring_finds_no_observation() {
+        let span = OBSERVATION_INTERVAL * OBSERVATION_CAPACITY as i64;
+        let now = 3 * span;
+        let mut observations = empty_observations();
+        record_every_second(&mut observations, 1, now);
+
+        assert!(observations.at_or_before(now - span).is_none());
+    }
+}